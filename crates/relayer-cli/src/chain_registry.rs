@@ -18,7 +18,7 @@ use ibc_relayer::{
         filter::{ChannelFilters, FilterPattern, PacketFilter},
         gas_multiplier::GasMultiplier,
         types::{MaxMsgNum, MaxTxSize, Memo},
-        {default, AddressType, ChainConfig, GasPrice},
+        {default, AddressType, ChainConfig, GasPrice, RetryPolicy},
     },
     keyring::Store,
 };
@@ -132,7 +132,12 @@ where
         packet_filter: packet_filter.unwrap_or_default(),
         address_type: AddressType::default(),
         sequential_batch_tx: false,
+        read_only: false,
         extension_options: Vec::new(),
+        handshake_retry: RetryPolicy::default(),
+        handshake_timeout: default::handshake_timeout(),
+        client_refresh_threshold: default::client_refresh_threshold(),
+        handshake_proofs_at_trusted_height: false,
     })
 }
 
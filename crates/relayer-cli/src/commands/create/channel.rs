@@ -1,14 +1,18 @@
+use std::path::PathBuf;
+
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 use console::style;
 use dialoguer::Confirm;
+use serde::Serialize;
+use subtle_encoding::hex;
 
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::requests::{
     IncludeProof, QueryClientStateRequest, QueryConnectionRequest, QueryHeight,
 };
-use ibc_relayer::channel::Channel;
+use ibc_relayer::channel::{Channel, HandshakeMessages, HandshakeStep};
 use ibc_relayer::connection::Connection;
 use ibc_relayer::foreign_client::ForeignClient;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
@@ -18,7 +22,7 @@ use ibc_relayer_types::core::ics04_channel::version::Version;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId, PortId};
 
 use crate::cli_utils::{spawn_chain_runtime, ChainHandlePair};
-use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::conclude::{exit_with_channel_error, exit_with_unrecoverable_error, Output};
 use crate::prelude::*;
 use ibc_relayer::config::default::connection_delay;
 
@@ -131,6 +135,61 @@ pub struct CreateChannelCommand {
         help = "Skip new_client_connection confirmation"
     )]
     yes: bool,
+
+    #[clap(
+        long = "dry-run",
+        conflicts_with = "new-client-connection",
+        help = "Only print the messages which the first handshake step would submit, without submitting them"
+    )]
+    dry_run: bool,
+
+    #[clap(
+        long = "reuse-existing",
+        conflicts_with_all = &["new-client-connection", "dry-run"],
+        help = "Return an open channel with the same ports and ordering on the connection, if there is one, instead of opening a new channel"
+    )]
+    reuse_existing: bool,
+
+    #[clap(
+        long = "state-file",
+        value_name = "PATH",
+        conflicts_with_all = &["new-client-connection", "dry-run", "reuse-existing"],
+        help = "JSON file to persist the handshake state to after every step. If the file holds the state of an interrupted handshake of the same ports, that handshake is resumed instead of opening a new channel"
+    )]
+    state_file: Option<PathBuf>,
+}
+
+/// The messages which `create channel --dry-run` would submit.
+#[derive(Debug, Serialize)]
+struct DryRunOutput {
+    step: HandshakeStep,
+    chain_id: ChainId,
+    messages: Vec<DryRunMessage>,
+}
+
+#[derive(Debug, Serialize)]
+struct DryRunMessage {
+    type_url: String,
+    /// The hex-encoded protobuf value of the message
+    value: String,
+}
+
+impl From<HandshakeMessages> for DryRunOutput {
+    fn from(msgs: HandshakeMessages) -> Self {
+        Self {
+            step: msgs.step,
+            chain_id: msgs.chain_id,
+            messages: msgs
+                .messages
+                .into_iter()
+                .map(|msg| DryRunMessage {
+                    type_url: msg.type_url,
+                    value: String::from_utf8(hex::encode(msg.value))
+                        .expect("hex encoding is valid UTF-8"),
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Runnable for CreateChannelCommand {
@@ -198,16 +257,20 @@ impl CreateChannelCommand {
             .unwrap_or_else(exit_with_unrecoverable_error);
 
         // Finally create the channel.
-        let channel = Channel::new(
+        let result = Channel::open(
             con,
             self.order,
             self.port_a.clone(),
             self.port_b.clone(),
             self.version.clone(),
         )
-        .unwrap_or_else(exit_with_unrecoverable_error);
+        .unwrap_or_else(exit_with_channel_error);
 
-        Output::success(channel).exit();
+        if result.already_open {
+            info!("channel {} is already open", result.channel);
+        }
+
+        Output::success(result.channel).exit();
     }
 
     /// Creates a new channel, reusing an already existing connection and its clients.
@@ -256,21 +319,63 @@ impl CreateChannelCommand {
         let connection = Connection::find(client_a, client_b, &identified_end)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
-        let channel = Channel::new(
-            connection,
-            self.order,
-            self.port_a.clone(),
-            self.port_b.clone(),
-            self.version.clone(),
-        )
-        .unwrap_or_else(exit_with_unrecoverable_error);
+        if self.dry_run {
+            let msgs = Channel::dry_run(
+                connection,
+                self.order,
+                self.port_a.clone(),
+                self.port_b.clone(),
+                self.version.clone(),
+            )
+            .unwrap_or_else(exit_with_channel_error);
+
+            Output::success(DryRunOutput::from(msgs)).exit();
+        }
+
+        if let Some(state_file) = &self.state_file {
+            let channel = Channel::new_with_state_file(
+                connection,
+                self.order,
+                self.port_a.clone(),
+                self.port_b.clone(),
+                self.version.clone(),
+                state_file,
+            )
+            .unwrap_or_else(exit_with_channel_error);
+
+            Output::success(channel).exit();
+        }
+
+        let result = if self.reuse_existing {
+            Channel::new_or_reuse(
+                connection,
+                self.order,
+                self.port_a.clone(),
+                self.port_b.clone(),
+                self.version.clone(),
+            )
+        } else {
+            Channel::open(
+                connection,
+                self.order,
+                self.port_a.clone(),
+                self.port_b.clone(),
+                self.version.clone(),
+            )
+        }
+        .unwrap_or_else(exit_with_channel_error);
 
-        Output::success(channel).exit();
+        if result.already_open {
+            info!("channel {} is already open", result.channel);
+        }
+
+        Output::success(result.channel).exit();
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
     use std::str::FromStr;
 
     use super::CreateChannelCommand;
@@ -292,7 +397,10 @@ mod tests {
                 order: Order::Unordered,
                 version: None,
                 new_client_connection: false,
-                yes: false
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -320,7 +428,10 @@ mod tests {
                 order: Order::Unordered,
                 version: Some(Version::new("v1".to_owned())),
                 new_client_connection: false,
-                yes: false
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -338,6 +449,159 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_create_channel_dry_run() {
+        assert_eq!(
+            CreateChannelCommand {
+                chain_a: ChainId::from_string("chain_a"),
+                chain_b: None,
+                connection_a: Some(ConnectionId::from_str("connection_a").unwrap()),
+                port_a: PortId::from_str("port_id_a").unwrap(),
+                port_b: PortId::from_str("port_id_b").unwrap(),
+                order: Order::Unordered,
+                version: None,
+                new_client_connection: false,
+                yes: false,
+                dry_run: true,
+                reuse_existing: false,
+                state_file: None
+            },
+            CreateChannelCommand::parse_from([
+                "test",
+                "--a-chain",
+                "chain_a",
+                "--a-connection",
+                "connection_a",
+                "--a-port",
+                "port_id_a",
+                "--b-port",
+                "port_id_b",
+                "--dry-run"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_create_channel_reuse_existing() {
+        assert_eq!(
+            CreateChannelCommand {
+                chain_a: ChainId::from_string("chain_a"),
+                chain_b: None,
+                connection_a: Some(ConnectionId::from_str("connection_a").unwrap()),
+                port_a: PortId::from_str("port_id_a").unwrap(),
+                port_b: PortId::from_str("port_id_b").unwrap(),
+                order: Order::Unordered,
+                version: None,
+                new_client_connection: false,
+                yes: false,
+                dry_run: false,
+                reuse_existing: true,
+                state_file: None
+            },
+            CreateChannelCommand::parse_from([
+                "test",
+                "--a-chain",
+                "chain_a",
+                "--a-connection",
+                "connection_a",
+                "--a-port",
+                "port_id_a",
+                "--b-port",
+                "port_id_b",
+                "--reuse-existing"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_create_channel_state_file() {
+        assert_eq!(
+            CreateChannelCommand {
+                chain_a: ChainId::from_string("chain_a"),
+                chain_b: None,
+                connection_a: Some(ConnectionId::from_str("connection_a").unwrap()),
+                port_a: PortId::from_str("port_id_a").unwrap(),
+                port_b: PortId::from_str("port_id_b").unwrap(),
+                order: Order::Unordered,
+                version: None,
+                new_client_connection: false,
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: Some(PathBuf::from("handshake.json"))
+            },
+            CreateChannelCommand::parse_from([
+                "test",
+                "--a-chain",
+                "chain_a",
+                "--a-connection",
+                "connection_a",
+                "--a-port",
+                "port_id_a",
+                "--b-port",
+                "port_id_b",
+                "--state-file",
+                "handshake.json"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_create_channel_state_file_with_reuse_existing() {
+        assert!(CreateChannelCommand::try_parse_from([
+            "test",
+            "--a-chain",
+            "chain_a",
+            "--a-connection",
+            "connection_a",
+            "--a-port",
+            "port_id_a",
+            "--b-port",
+            "port_id_b",
+            "--state-file",
+            "handshake.json",
+            "--reuse-existing"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_create_channel_reuse_existing_with_new_client_conn() {
+        assert!(CreateChannelCommand::try_parse_from([
+            "test",
+            "--a-chain",
+            "chain_a",
+            "--b-chain",
+            "chain_b",
+            "--a-port",
+            "port_id_a",
+            "--b-port",
+            "port_id_b",
+            "--new-client-connection",
+            "--reuse-existing"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_create_channel_dry_run_with_new_client_conn() {
+        assert!(CreateChannelCommand::try_parse_from([
+            "test",
+            "--a-chain",
+            "chain_a",
+            "--b-chain",
+            "chain_b",
+            "--a-port",
+            "port_id_a",
+            "--b-port",
+            "port_id_b",
+            "--new-client-connection",
+            "--yes",
+            "--dry-run"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_create_channel_order() {
         assert_eq!(
@@ -350,7 +614,10 @@ mod tests {
                 order: Order::Ordered,
                 version: None,
                 new_client_connection: false,
-                yes: false
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -380,7 +647,10 @@ mod tests {
                 order: Order::Unordered,
                 version: None,
                 new_client_connection: false,
-                yes: false
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -466,7 +736,10 @@ mod tests {
                 order: Order::Unordered,
                 version: None,
                 new_client_connection: true,
-                yes: false
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -495,7 +768,10 @@ mod tests {
                 order: Order::Unordered,
                 version: None,
                 new_client_connection: true,
-                yes: true
+                yes: true,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -525,7 +801,10 @@ mod tests {
                 order: Order::Unordered,
                 version: None,
                 new_client_connection: true,
-                yes: false
+                yes: false,
+                dry_run: false,
+                reuse_existing: false,
+                state_file: None
             },
             CreateChannelCommand::parse_from([
                 "test",
@@ -20,6 +20,10 @@ impl Runnable for HealthCheckCmd {
 
             info!("performing health check...");
 
+            if ch.read_only {
+                info!("chain is in read-only mode, no transaction will be submitted to it");
+            }
+
             let chain =
                 spawn_chain_runtime(&config, &ch.id).unwrap_or_else(exit_with_unrecoverable_error);
 
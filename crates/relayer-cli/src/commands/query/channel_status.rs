@@ -0,0 +1,174 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::channel::{Channel, HandshakeStep};
+use ibc_relayer::object::Channel as WorkerChannelObject;
+use ibc_relayer_types::core::ics04_channel::channel::State;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The progress of the handshake of a channel.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum HandshakeStatus {
+    /// Both channel ends are open.
+    Complete,
+    /// The handshake is under way, `next_step` is the step to perform next.
+    InProgress { next_step: HandshakeStep },
+    /// The channel ends are in a combination of states which the handshake
+    /// cannot progress from, eg. one of them is closed.
+    Stuck,
+}
+
+impl HandshakeStatus {
+    fn from_states(a_state: State, b_state: State) -> Self {
+        match HandshakeStep::from_states(a_state, b_state) {
+            Some(HandshakeStep::Open) => Self::Complete,
+            Some(next_step) => Self::InProgress { next_step },
+            None => Self::Stuck,
+        }
+    }
+}
+
+/// The state of the two ends of a channel.
+#[derive(Debug, Serialize)]
+struct ChannelStatus {
+    chain_a: ChainId,
+    port_a: PortId,
+    channel_a: ChannelId,
+    state_a: State,
+    chain_b: ChainId,
+    port_b: PortId,
+    channel_b: Option<ChannelId>,
+    state_b: State,
+    handshake: HandshakeStatus,
+}
+
+/// Queries the state of both ends of a channel, given one of them, and reports
+/// whether the channel handshake is complete, in progress or stuck.
+/// This command never submits anything to the chains.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryChannelStatusCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain at one end of the channel"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+}
+
+impl QueryChannelStatusCmd {
+    fn execute(&self) -> Result<ChannelStatus, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let height = chains.src.query_latest_height().map_err(Error::relayer)?;
+
+        let (channel, _) = Channel::restore_from_state(
+            chains.src.clone(),
+            chains.dst.clone(),
+            WorkerChannelObject {
+                dst_chain_id: chains.dst.id(),
+                src_chain_id: chains.src.id(),
+                src_channel_id: self.channel_id.clone(),
+                src_port_id: self.port_id.clone(),
+            },
+            height,
+        )
+        .map_err(Error::channel)?;
+
+        let (state_a, state_b) = channel.query_status().map_err(Error::channel)?;
+
+        Ok(ChannelStatus {
+            chain_a: chains.src.id(),
+            port_a: channel.src_port_id().clone(),
+            channel_a: self.channel_id.clone(),
+            state_a,
+            chain_b: chains.dst.id(),
+            port_b: channel.dst_port_id().clone(),
+            channel_b: channel.dst_channel_id().cloned(),
+            state_b,
+            handshake: HandshakeStatus::from_states(state_a, state_b),
+        })
+    }
+}
+
+impl Runnable for QueryChannelStatusCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(status) => Output::success(status).exit(),
+            Err(e) => Output::cli_error(&e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryChannelStatusCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_query_channel_status() {
+        assert_eq!(
+            QueryChannelStatusCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+            },
+            QueryChannelStatusCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_channel_status_no_chan() {
+        assert!(QueryChannelStatusCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--port", "port_id"
+        ])
+        .is_err())
+    }
+}
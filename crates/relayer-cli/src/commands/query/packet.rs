@@ -7,6 +7,7 @@ mod commitment;
 mod commitments;
 mod pending;
 mod pending_acks;
+mod pending_packets;
 mod pending_sends;
 mod util;
 
@@ -32,4 +33,7 @@ pub enum QueryPacketCmds {
 
     /// Output a summary of pending packets in both directions
     Pending(pending::QueryPendingPacketsCmd),
+
+    /// List the details of the packets sent over a channel and not received yet
+    PendingPackets(pending_packets::QueryPendingPacketsDetailsCmd),
 }
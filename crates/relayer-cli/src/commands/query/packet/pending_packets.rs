@@ -0,0 +1,123 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::channel::{Channel, PendingPacket};
+use ibc_relayer::object::Channel as WorkerChannelObject;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Lists the packets sent by the chain over the given channel which the counterparty
+/// chain did not receive yet, with their timeouts and data.
+/// This command never submits anything to the chains.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryPendingPacketsDetailsCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain which sent the packets"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+}
+
+impl QueryPendingPacketsDetailsCmd {
+    fn execute(&self) -> Result<Vec<PendingPacket>, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let height = chains.src.query_latest_height().map_err(Error::relayer)?;
+
+        let (channel, _) = Channel::restore_from_state(
+            chains.src.clone(),
+            chains.dst.clone(),
+            WorkerChannelObject {
+                dst_chain_id: chains.dst.id(),
+                src_chain_id: chains.src.id(),
+                src_channel_id: self.channel_id.clone(),
+                src_port_id: self.port_id.clone(),
+            },
+            height,
+        )
+        .map_err(Error::channel)?;
+
+        channel.scan_pending_packets().map_err(Error::channel)
+    }
+}
+
+impl Runnable for QueryPendingPacketsDetailsCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(packets) => Output::success(packets).exit(),
+            Err(e) => Output::cli_error(&e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryPendingPacketsDetailsCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_query_packet_pending_packets() {
+        assert_eq!(
+            QueryPendingPacketsDetailsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap()
+            },
+            QueryPendingPacketsDetailsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_pending_packets_no_chan() {
+        assert!(QueryPendingPacketsDetailsCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--port", "port_id"
+        ])
+        .is_err())
+    }
+}
@@ -38,6 +38,9 @@ pub enum TxCmd {
     /// Confirm opening of a channel (ChannelOpenConfirm)
     ChanOpenConfirm(channel::TxChanOpenConfirmCmd),
 
+    /// Run the channel opening handshake to completion, optionally resuming from a given step
+    ChanHandshake(channel::TxChanHandshakeCmd),
+
     /// Initiate the closing of a channel (ChannelCloseInit)
     ChanCloseInit(channel::TxChanCloseInitCmd),
 
@@ -3,7 +3,7 @@ use abscissa_core::{Command, Runnable};
 
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::requests::{IncludeProof, QueryConnectionRequest, QueryHeight};
-use ibc_relayer::channel::{Channel, ChannelSide};
+use ibc_relayer::channel::{Channel, ChannelSide, HandshakeStep};
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
 use ibc_relayer_types::core::ics04_channel::channel::Order;
 use ibc_relayer_types::core::ics24_host::identifier::{
@@ -46,7 +46,7 @@ macro_rules! tx_chan_cmd {
 
         match res {
             Ok(receipt) => Output::success(receipt).exit(),
-            Err(e) => Output::error(format!("{}", e)).exit(),
+            Err(e) => Output::cli_error(&e).exit(),
         }
     };
 }
@@ -131,6 +131,7 @@ impl Runnable for TxChanOpenInitCmd {
 
         let channel = Channel {
             connection_delay: Default::default(),
+            overall_deadline: None,
             ordering: self.order,
             a_side: ChannelSide::new(
                 chains.src,
@@ -158,7 +159,7 @@ impl Runnable for TxChanOpenInitCmd {
 
         match res {
             Ok(receipt) => Output::success(receipt).exit(),
-            Err(e) => Output::error(format!("{}", e)).exit(),
+            Err(e) => Output::cli_error(&e).exit(),
         }
     }
 }
@@ -239,6 +240,7 @@ impl Runnable for TxChanOpenTryCmd {
             |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
                 Channel {
                     connection_delay: Default::default(),
+                    overall_deadline: None,
                     ordering: Order::default(),
                     a_side: ChannelSide::new(
                         chains.src,
@@ -340,6 +342,7 @@ impl Runnable for TxChanOpenAckCmd {
             |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
                 Channel {
                     connection_delay: Default::default(),
+                    overall_deadline: None,
                     ordering: Order::default(),
                     a_side: ChannelSide::new(
                         chains.src,
@@ -441,6 +444,7 @@ impl Runnable for TxChanOpenConfirmCmd {
             |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
                 Channel {
                     connection_delay: Default::default(),
+                    overall_deadline: None,
                     ordering: Order::default(),
                     a_side: ChannelSide::new(
                         chains.src,
@@ -542,6 +546,7 @@ impl Runnable for TxChanCloseInitCmd {
             |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
                 Channel {
                     connection_delay: Default::default(),
+                    overall_deadline: None,
                     ordering: Order::default(),
                     a_side: ChannelSide::new(
                         chains.src,
@@ -643,6 +648,7 @@ impl Runnable for TxChanCloseConfirmCmd {
             |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
                 Channel {
                     connection_delay: Default::default(),
+                    overall_deadline: None,
                     ordering: Order::default(),
                     a_side: ChannelSide::new(
                         chains.src,
@@ -666,16 +672,163 @@ impl Runnable for TxChanCloseConfirmCmd {
     }
 }
 
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanHandshakeCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        value_name = "SRC_CHANNEL_ID",
+        help = "Identifier of the source channel, if it exists already"
+    )]
+    src_chan_id: Option<ChannelId>,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        value_name = "DST_CHANNEL_ID",
+        help = "Identifier of the destination channel, if it exists already"
+    )]
+    dst_chan_id: Option<ChannelId>,
+
+    #[clap(
+        long = "order",
+        default_value_t,
+        value_name = "ORDER",
+        help = "The channel ordering, valid options 'unordered' (default) and 'ordered'"
+    )]
+    order: Order,
+
+    #[clap(
+        long = "resume",
+        value_name = "STEP",
+        help = "The handshake step to resume from, valid options 'init', 'try', 'ack' and 'confirm'. \
+                Defaults to 'init', or to 'try' if a channel identifier is given"
+    )]
+    resume: Option<HandshakeStep>,
+}
+
+impl Runnable for TxChanHandshakeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chains = match ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id) {
+            Ok(chains) => chains,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        // Retrieve the connection
+        let dst_connection = match chains.dst.query_connection(
+            QueryConnectionRequest {
+                connection_id: self.dst_conn_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        ) {
+            Ok((connection, _)) => connection,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        let src_conn_id = match dst_connection.counterparty().connection_id() {
+            Some(src_conn_id) => src_conn_id.clone(),
+            None => Output::error(format!(
+                "connection '{}' on chain '{}' has no counterparty connection yet",
+                self.dst_conn_id, self.dst_chain_id
+            ))
+            .exit(),
+        };
+
+        let mut channel = Channel {
+            connection_delay: dst_connection.delay_period(),
+            overall_deadline: None,
+            ordering: self.order,
+            a_side: ChannelSide::new(
+                chains.src,
+                dst_connection.counterparty().client_id().clone(),
+                src_conn_id,
+                self.src_port_id.clone(),
+                self.src_chan_id.clone(),
+                None,
+            ),
+            b_side: ChannelSide::new(
+                chains.dst,
+                dst_connection.client_id().clone(),
+                self.dst_conn_id.clone(),
+                self.dst_port_id.clone(),
+                self.dst_chan_id.clone(),
+                None,
+            ),
+        };
+
+        let from = self
+            .resume
+            .unwrap_or_else(|| channel.first_handshake_step());
+
+        info!("channel handshake from {}: {}", from, channel);
+
+        match channel.resume_handshake(from).map_err(Error::channel) {
+            Ok(result) => Output::success(result).exit(),
+            Err(e) => Output::cli_error(&e).exit(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        TxChanCloseConfirmCmd, TxChanCloseInitCmd, TxChanOpenAckCmd, TxChanOpenConfirmCmd,
-        TxChanOpenInitCmd, TxChanOpenTryCmd,
+        TxChanCloseConfirmCmd, TxChanCloseInitCmd, TxChanHandshakeCmd, TxChanOpenAckCmd,
+        TxChanOpenConfirmCmd, TxChanOpenInitCmd, TxChanOpenTryCmd,
     };
 
     use std::str::FromStr;
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer::channel::HandshakeStep;
     use ibc_relayer_types::core::{
         ics04_channel::channel::Order,
         ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId},
@@ -1861,4 +2014,88 @@ mod tests {
         ])
         .is_err())
     }
+
+    #[test]
+    fn test_chan_handshake_required_only() {
+        assert_eq!(
+            TxChanHandshakeCmd {
+                dst_chain_id: ChainId::from_string("chain_b"),
+                src_chain_id: ChainId::from_string("chain_a"),
+                dst_conn_id: ConnectionId::from_str("connection_b").unwrap(),
+                dst_port_id: PortId::from_str("port_b").unwrap(),
+                src_port_id: PortId::from_str("port_a").unwrap(),
+                src_chan_id: None,
+                dst_chan_id: None,
+                order: Order::Unordered,
+                resume: None
+            },
+            TxChanHandshakeCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_b",
+                "--src-chain",
+                "chain_a",
+                "--dst-connection",
+                "connection_b",
+                "--dst-port",
+                "port_b",
+                "--src-port",
+                "port_a"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_chan_handshake_resume() {
+        assert_eq!(
+            TxChanHandshakeCmd {
+                dst_chain_id: ChainId::from_string("chain_b"),
+                src_chain_id: ChainId::from_string("chain_a"),
+                dst_conn_id: ConnectionId::from_str("connection_b").unwrap(),
+                dst_port_id: PortId::from_str("port_b").unwrap(),
+                src_port_id: PortId::from_str("port_a").unwrap(),
+                src_chan_id: Some(ChannelId::from_str("channel-1").unwrap()),
+                dst_chan_id: None,
+                order: Order::Unordered,
+                resume: Some(HandshakeStep::Ack)
+            },
+            TxChanHandshakeCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_b",
+                "--src-chain",
+                "chain_a",
+                "--dst-connection",
+                "connection_b",
+                "--dst-port",
+                "port_b",
+                "--src-port",
+                "port_a",
+                "--src-channel",
+                "channel-1",
+                "--resume",
+                "ack"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_chan_handshake_unknown_step() {
+        assert!(TxChanHandshakeCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_b",
+            "--src-chain",
+            "chain_a",
+            "--dst-connection",
+            "connection_b",
+            "--dst-port",
+            "port_b",
+            "--src-port",
+            "port_a",
+            "--resume",
+            "close"
+        ])
+        .is_err())
+    }
 }
@@ -28,10 +28,11 @@ impl JsonTracing {
         let use_color = false;
 
         // Construct a tracing subscriber with the supplied filter and enable reloading.
+        // Logs go to stderr, so that stdout only carries the JSON result of the command.
         let builder = FmtSubscriber::builder()
             .with_target(false)
             .with_env_filter(filter)
-            .with_writer(std::io::stdout)
+            .with_writer(std::io::stderr)
             .with_ansi(use_color)
             .with_thread_ids(true)
             .json();
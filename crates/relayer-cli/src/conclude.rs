@@ -61,6 +61,10 @@ use core::fmt;
 use serde::Serialize;
 use tracing::warn;
 
+use ibc_relayer::channel::error::ChannelErrorDetail;
+use ibc_relayer::channel::ChannelError;
+
+use crate::error::{Error, ErrorDetail};
 use crate::prelude::app_reader;
 
 /// Functional-style method to exit a program.
@@ -78,6 +82,10 @@ pub fn exit_with(out: Output) -> ! {
             Status::Error => style("ERROR").red(),
         };
         println!("{} {}", status, out.result);
+
+        if let Some(hint) = out.hint {
+            println!("{} {}: {}", style("HINT").yellow(), hint.code, hint.hint);
+        }
     }
 
     // The return code
@@ -116,6 +124,18 @@ pub fn exit_with_unrecoverable_error<T, E: fmt::Display>(err: E) -> T {
     Output::error(format!("{}", err)).exit()
 }
 
+/// Exits the program with a channel error, reporting its code and remediation hint
+/// along with the error message.
+///
+/// ## Example of use
+/// ```ignore
+/// let channel = Channel::new(connection, ordering, a_port, b_port, version)
+///     .unwrap_or_else(exit_with_channel_error);
+/// ```
+pub fn exit_with_channel_error<T>(err: ChannelError) -> T {
+    Output::channel_error(&err).exit()
+}
+
 /// The result to display before quitting, can either be a JSON value, some plain text,
 /// a value to print with its Debug instance, or nothing.
 #[derive(Debug)]
@@ -147,6 +167,25 @@ pub struct Output {
 
     /// The result of a command, such as the output from a query or transaction.
     pub result: Result,
+
+    /// The code and remediation hint of the error which the command failed with, if known.
+    pub hint: Option<ErrorHint>,
+}
+
+/// The stable code of an error and a short hint on how to remediate it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ErrorHint {
+    pub code: &'static str,
+    pub hint: &'static str,
+}
+
+impl From<&ChannelErrorDetail> for ErrorHint {
+    fn from(detail: &ChannelErrorDetail) -> Self {
+        ErrorHint {
+            code: detail.code(),
+            hint: detail.hint(),
+        }
+    }
 }
 
 impl Output {
@@ -155,6 +194,7 @@ impl Output {
         Output {
             status,
             result: Result::Nothing,
+            hint: None,
         }
     }
 
@@ -202,6 +242,29 @@ impl Output {
         Output::with_error().with_msg(msg)
     }
 
+    /// Quick-access constructor for an output signalling a channel error, along with the code
+    /// and remediation hint of the error.
+    pub fn channel_error(e: &ChannelError) -> Self {
+        Output::error(e).with_hint(e.detail().into())
+    }
+
+    /// Quick-access constructor for an output signalling an error of a command. The code and
+    /// remediation hint of the error are reported when it wraps a channel error.
+    pub fn cli_error(e: &Error) -> Self {
+        let output = Output::error(e);
+
+        match e.detail() {
+            ErrorDetail::Channel(e) => output.with_hint((&e.source).into()),
+            _ => output,
+        }
+    }
+
+    /// Builder-style method for attaching the code and remediation hint of an error.
+    pub fn with_hint(mut self, hint: ErrorHint) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
     /// Quick-access constructor for an output signalling a success `status` and tagged with the
     /// input `result`.
     pub fn success_msg(msg: impl ToString) -> Self {
@@ -231,6 +294,11 @@ impl Output {
 
         map.insert("result".to_string(), value);
 
+        if let Some(hint) = self.hint {
+            map.insert("code".to_string(), hint.code.into());
+            map.insert("hint".to_string(), hint.hint.into());
+        }
+
         serde_json::Value::Object(map)
     }
 }
@@ -271,3 +339,26 @@ impl fmt::Display for Status {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_errors_carry_their_code_and_hint() {
+        let e = ChannelError::missing_local_channel_id();
+        let json = Output::channel_error(&e).into_json();
+
+        assert_eq!(json["status"], "error");
+        assert_eq!(json["result"], e.to_string());
+        assert_eq!(json["code"], e.code());
+        assert_eq!(json["hint"], e.hint());
+
+        let code = e.code();
+        let json = Output::cli_error(&Error::channel(e)).into_json();
+        assert_eq!(json["code"], code);
+
+        let json = Output::error("no hint").into_json();
+        assert!(json.get("code").is_none());
+    }
+}
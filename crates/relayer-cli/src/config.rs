@@ -60,6 +60,16 @@ define_error! {
                     e.threshold, e.chain_id, e.reason)
             },
 
+        InvalidClientRefreshThreshold
+            {
+                threshold: f64,
+                chain_id: ChainId,
+            }
+            |e| {
+                format!("config file specifies an invalid `client_refresh_threshold` ({0}) for the chain '{1}', it must be strictly between 0 and 1",
+                    e.threshold, e.chain_id)
+            },
+
         DeprecatedGasAdjustment
             {
                 gas_adjustment: f64,
@@ -94,6 +104,8 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         validate_trust_threshold(&c.id, c.trust_threshold)?;
 
+        validate_client_refresh_threshold(&c.id, c.client_refresh_threshold)?;
+
         // Validate gas-related settings
         validate_gas_settings(&c.id, c)?;
     }
@@ -156,6 +168,22 @@ fn validate_trust_threshold(
     Ok(())
 }
 
+/// Check that the client refresh threshold is strictly between 0 and 1, otherwise
+/// clients would either never be refreshed or be refreshed continuously.
+fn validate_client_refresh_threshold(
+    id: &ChainId,
+    threshold: f64,
+) -> Result<(), Diagnostic<Error>> {
+    if threshold.is_nan() || threshold <= 0.0 || threshold >= 1.0 {
+        return Err(Diagnostic::Error(Error::invalid_client_refresh_threshold(
+            threshold,
+            id.clone(),
+        )));
+    }
+
+    Ok(())
+}
+
 fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
     // Check that the gas_adjustment option is not set
     if let Some(gas_adjustment) = config.gas_adjustment {
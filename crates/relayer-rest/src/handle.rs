@@ -8,7 +8,7 @@ use ibc_relayer::supervisor::dump_state::SupervisorState;
 use ibc_relayer::{
     config::ChainConfig,
     rest::{
-        request::{reply_channel, ReplySender, Request, VersionInfo},
+        request::{reply_channel, HealthReport, ReplySender, Request, VersionInfo},
         RestApiError,
     },
 };
@@ -64,6 +64,10 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+pub fn health(sender: &channel::Sender<Request>) -> Result<HealthReport, RestApiError> {
+    submit_request(sender, |reply_to| Request::Health { reply_to })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
@@ -7,7 +7,7 @@ use tracing::{info, trace};
 use ibc_relayer::rest::request::Request;
 
 use crate::{
-    handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state},
+    handle::{all_chain_ids, assemble_version_info, chain_config, health, supervisor_state},
     Config,
 };
 
@@ -82,6 +82,16 @@ fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (GET) (/health) => {
+                trace!("[rest] GET /health");
+                match health(&sender) {
+                    Ok(report) => rouille::Response::json(&report),
+                    // The relayer itself is unresponsive
+                    Err(e) => rouille::Response::json(&JsonResult::<(), _>::Error(e))
+                        .with_status_code(503),
+                }
+            },
+
             _ => rouille::Response::empty_404(),
         )
     })
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use ibc_relayer::{
     config::ChainConfig,
-    rest::request::{Request, VersionInfo},
+    rest::request::{ChainHealth, HealthReport, HealthStatus, Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -137,3 +137,31 @@ fn state() {
         req => TestResult::WrongRequest(req),
     });
 }
+
+#[test]
+fn health() {
+    let report = HealthReport {
+        status: HealthStatus::Degraded,
+        chains: [
+            (
+                "mock-0".parse().unwrap(),
+                ChainHealth {
+                    connected: true,
+                    latest_height: Some(10),
+                    read_only: false,
+                },
+            ),
+            ("mock-1".parse().unwrap(), ChainHealth::disconnected(true)),
+        ]
+        .into_iter()
+        .collect(),
+    };
+
+    run_test(19105, "/health", report.clone(), |req| match req {
+        Request::Health { reply_to } => {
+            reply_to.send(Ok(report)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    });
+}
@@ -589,6 +589,21 @@ mod tests {
                 want_res: Order::Ordered,
                 want_err: false,
             },
+            Test {
+                ordering: "ordered",
+                want_res: Order::Ordered,
+                want_err: false,
+            },
+            Test {
+                ordering: "Unordered",
+                want_res: Order::Unordered,
+                want_err: false,
+            },
+            Test {
+                ordering: "ORDER_ORDERED",
+                want_res: Order::Ordered,
+                want_err: false,
+            },
             Test {
                 ordering: "UNKNOWN_ORDER",
                 want_res: Order::None,
@@ -275,7 +275,30 @@ pub enum IbcEvent {
 
     AppModule(ModuleEvent),
 
-    ChainError(String), // Special event, signifying an error on CheckTx or DeliverTx
+    ChainError(ChainError), // Special event, signifying an error on CheckTx or DeliverTx
+}
+
+/// The error reported by a chain for a transaction, either when checking it
+/// on `broadcast_tx_sync` or when delivering it in a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ChainError {
+    /// Where the error was reported, e.g. `deliver_tx on chain ibc-0`.
+    pub origin: String,
+    pub tx_hash: String,
+    pub code: u32,
+    /// The module which defines `code`, e.g. `sdk` for the Cosmos SDK errors.
+    pub codespace: String,
+    pub log: String,
+}
+
+impl Display for ChainError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "{} for Tx hash {} reports error: code={}, codespace={}, log={:?}",
+            self.origin, self.tx_hash, self.code, self.codespace, self.log
+        )
+    }
 }
 
 impl Display for IbcEvent {
@@ -2,7 +2,7 @@ use core::mem;
 
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ChainError, IbcEvent};
 use ibc_relayer_types::Height;
 use prost::Message;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
@@ -255,10 +255,17 @@ fn response_to_tx_sync_result(
         // once we remove the `ChainError` event (which is not actually an event)
         let height = Height::new(chain_id.version(), 1).unwrap();
 
-        let events_per_tx = vec![IbcEventWithHeight::new(IbcEvent::ChainError(format!(
-            "check_tx (broadcast_tx_sync) on chain {} for Tx hash {} reports error: code={:?}, log={:?}",
-            chain_id, response.hash, response.code, response.log
-        )), height); message_count];
+        let error = ChainError {
+            origin: format!("check_tx (broadcast_tx_sync) on chain {}", chain_id),
+            tx_hash: response.hash.to_string(),
+            code: response.code.value(),
+            // `broadcast_tx_sync` responses do not report the codespace of the error
+            codespace: String::new(),
+            log: response.log.clone(),
+        };
+
+        let events_per_tx =
+            vec![IbcEventWithHeight::new(IbcEvent::ChainError(error), height); message_count];
 
         TxSyncResult {
             response,
@@ -345,19 +352,22 @@ fn batch_messages(
 
 #[cfg(test)]
 mod tests {
-    use super::batch_messages;
+    use super::{batch_messages, response_to_tx_sync_result};
     use crate::chain::cosmos::encode::sign_and_encode_tx;
     use crate::chain::cosmos::gas::gas_amount_to_fee;
     use crate::chain::cosmos::types::account::{
         Account, AccountAddress, AccountNumber, AccountSequence,
     };
     use crate::chain::cosmos::types::config::TxConfig;
+    use crate::channel::ChannelError;
     use crate::config;
     use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
     use crate::keyring::{self, KeyRing, Secp256k1KeyPair, SigningKeyPair};
     use ibc_proto::google::protobuf::Any;
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::events::IbcEvent;
     use std::fs;
+    use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
     const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
 
@@ -634,4 +644,31 @@ mod tests {
             vec![],
         );
     }
+
+    #[test]
+    fn check_tx_errors_are_retryable() {
+        let chain_id = ChainId::from_string("chain_id");
+
+        // Rejected by `CheckTx` as unauthorized, which would be permanent in the `sdk` codespace
+        let response = Response {
+            code: 4.into(),
+            data: Default::default(),
+            log: "unauthorized".to_string(),
+            hash: Default::default(),
+        };
+
+        let result = response_to_tx_sync_result(&chain_id, 2, response);
+        assert_eq!(result.events.len(), 2);
+
+        for event in result.events {
+            match event.event {
+                IbcEvent::ChainError(error) => {
+                    assert_eq!(error.code, 4);
+                    assert!(error.codespace.is_empty());
+                    assert!(ChannelError::tx_response(chain_id.clone(), error).is_retryable());
+                }
+                event => panic!("unexpected event: {}", event),
+            }
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ChainError, IbcEvent};
 use ibc_relayer_types::Height as ICSHeight;
 use tendermint::abci::Event;
 use tendermint::Hash as TxHash;
@@ -349,10 +349,13 @@ pub fn all_ibc_events_from_tx_search_response(
         // We can only return a single ChainError here because at this point
         // we have lost information about how many messages were in the transaction
         vec![IbcEventWithHeight::new(
-            IbcEvent::ChainError(format!(
-                "deliver_tx for {} reports error: code={:?}, log={:?}",
-                response.hash, deliver_tx_result.code, deliver_tx_result.log
-            )),
+            IbcEvent::ChainError(ChainError {
+                origin: format!("deliver_tx on chain {}", chain_id),
+                tx_hash: response.hash.to_string(),
+                code: deliver_tx_result.code.value(),
+                codespace: deliver_tx_result.codespace,
+                log: deliver_tx_result.log,
+            }),
             height,
         )]
     } else {
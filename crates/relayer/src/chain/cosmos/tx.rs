@@ -124,7 +124,7 @@ pub async fn batched_send_tx(
 
     for event in &events {
         if let IbcEvent::ChainError(ref e) = event.event {
-            return Err(Error::send_tx(e.clone()));
+            return Err(Error::send_tx(e.to_string()));
         }
     }
 
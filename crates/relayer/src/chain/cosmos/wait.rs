@@ -1,6 +1,6 @@
 use core::time::Duration;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ChainError, IbcEvent};
 use ibc_relayer_types::Height;
 use itertools::Itertools;
 use std::thread;
@@ -89,10 +89,13 @@ async fn update_tx_sync_result(
             if response.tx_result.code.is_err() {
                 tx_sync_result.events = vec![
                     IbcEventWithHeight::new(
-                        IbcEvent::ChainError(format!(
-                            "deliver_tx for {} reports error: code={:?}, log={:?}",
-                            response.hash, response.tx_result.code, response.tx_result.log
-                        )),
+                        IbcEvent::ChainError(ChainError {
+                            origin: format!("deliver_tx on chain {}", chain_id),
+                            tx_hash: response.hash.to_string(),
+                            code: response.tx_result.code.value(),
+                            codespace: response.tx_result.codespace.clone(),
+                            log: response.tx_result.log.clone(),
+                        }),
                         height
                     );
                     message_count
@@ -141,6 +141,11 @@ where
 
                     let _span = span.entered();
 
+                    let event = match refuse_if_read_only(self.chain.config(), event)? {
+                        Some(event) => event,
+                        None => continue,
+                    };
+
                     match event {
                         ChainRequest::Shutdown { reply_to } => {
                             let res = self.chain.shutdown();
@@ -833,3 +838,135 @@ where
         Ok(())
     }
 }
+
+/// Replies with [`Error::read_only_mode`] to the requests which submit a transaction,
+/// if the chain is configured in read-only mode, so that nothing is ever broadcast
+/// to such a chain. Returns the request if it may be handled.
+fn refuse_if_read_only(
+    config: &ChainConfig,
+    request: ChainRequest,
+) -> Result<Option<ChainRequest>, Error> {
+    if !config.read_only {
+        return Ok(Some(request));
+    }
+
+    let refused = || Error::read_only_mode(config.id.clone());
+
+    match request {
+        ChainRequest::SendMessagesAndWaitCommit { reply_to, .. } => {
+            reply_to.send(Err(refused())).map_err(Error::send)?
+        }
+        ChainRequest::SendMessagesAndWaitCheckTx { reply_to, .. } => {
+            reply_to.send(Err(refused())).map_err(Error::send)?
+        }
+        ChainRequest::MaybeRegisterCounterpartyPayee { reply_to, .. } => {
+            reply_to.send(Err(refused())).map_err(Error::send)?
+        }
+        request => return Ok(Some(request)),
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::handle::{BaseChainHandle, ReplyTo};
+
+    /// A chain handle whose requests are all forwarded to `requests`.
+    fn forwarding_handle(
+        config: &ChainConfig,
+    ) -> (BaseChainHandle, channel::Receiver<(Span, ChainRequest)>) {
+        let (sender, receiver) = channel::unbounded();
+        (BaseChainHandle::new(config.id.clone(), sender), receiver)
+    }
+
+    fn test_chain_config(read_only: bool) -> ChainConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = crate::config::load(path).expect("could not parse config");
+
+        ChainConfig {
+            read_only,
+            ..config.chains[0].clone()
+        }
+    }
+
+    /// Runs `submit` against a chain in the given mode, and returns its result along
+    /// with the number of requests which reached the chain endpoint.
+    fn submit_to_chain<T: Send + 'static>(
+        read_only: bool,
+        submit: impl FnOnce(BaseChainHandle) -> Result<T, Error> + Send + 'static,
+    ) -> (Result<T, Error>, usize) {
+        let config = test_chain_config(read_only);
+        let (handle, requests) = forwarding_handle(&config);
+
+        let submission = thread::spawn(move || submit(handle));
+
+        let mut handled = 0;
+        for (_, request) in requests {
+            if let Some(request) = refuse_if_read_only(&config, request).unwrap() {
+                handled += 1;
+                // Stand in for the chain endpoint by failing every request it handles
+                reply_with_error(request);
+            }
+        }
+
+        (submission.join().unwrap(), handled)
+    }
+
+    fn reply_with_error(request: ChainRequest) {
+        fn fail<T>(reply_to: ReplyTo<T>) {
+            reply_to.send(Err(Error::channel_send())).unwrap();
+        }
+
+        match request {
+            ChainRequest::SendMessagesAndWaitCommit { reply_to, .. } => fail(reply_to),
+            ChainRequest::SendMessagesAndWaitCheckTx { reply_to, .. } => fail(reply_to),
+            ChainRequest::MaybeRegisterCounterpartyPayee { reply_to, .. } => fail(reply_to),
+            ChainRequest::QueryApplicationStatus { reply_to } => fail(reply_to),
+            _ => panic!("unexpected request"),
+        }
+    }
+
+    #[test]
+    fn read_only_chains_submit_no_transaction() {
+        let msgs = || TrackedMsgs::new_static(vec![], "test");
+
+        let (result, handled) = submit_to_chain(true, move |chain| {
+            chain.send_messages_and_wait_commit(msgs())
+        });
+        assert!(result.unwrap_err().is_read_only_mode_error());
+        assert_eq!(handled, 0);
+
+        let (result, handled) = submit_to_chain(true, move |chain| {
+            chain.send_messages_and_wait_check_tx(msgs())
+        });
+        assert!(result.unwrap_err().is_read_only_mode_error());
+        assert_eq!(handled, 0);
+
+        let (result, handled) = submit_to_chain(true, |chain| {
+            chain.maybe_register_counterparty_payee(
+                ChannelId::default(),
+                PortId::transfer(),
+                "cosmos1payee".parse().unwrap(),
+            )
+        });
+        assert!(result.unwrap_err().is_read_only_mode_error());
+        assert_eq!(handled, 0);
+
+        // Queries are still answered by the chain
+        let (result, handled) = submit_to_chain(true, |chain| chain.query_application_status());
+        assert!(!result.unwrap_err().is_read_only_mode_error());
+        assert_eq!(handled, 1);
+
+        let (result, handled) = submit_to_chain(false, move |chain| {
+            chain.send_messages_and_wait_commit(msgs())
+        });
+        assert!(!result.unwrap_err().is_read_only_mode_error());
+        assert_eq!(handled, 1);
+    }
+}
@@ -1,11 +1,25 @@
+// Channel and counterparty identifiers are optional until the corresponding
+// handshake step has happened, make sure they are never unwrapped.
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+
 use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
 use core::time::Duration;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+
+use crossbeam_channel::{select, RecvError};
 
 use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use itertools::Either;
 use serde::Serialize;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 pub use error::ChannelError;
+use ibc_relayer_types::core::ics02_client::header::Header;
+use ibc_relayer_types::core::ics02_client::msgs::update_client::{self, MsgUpdateClient};
 use ibc_relayer_types::core::ics04_channel::channel::{
     ChannelEnd, Counterparty, IdentifiedChannelEnd, Order, State,
 };
@@ -15,68 +29,69 @@ use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenA
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use ibc_relayer_types::core::ics24_host::identifier::{
-    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+    ChainId, ChannelId, ClientId, ConnectionId, PortChannelId, PortId,
 };
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
+use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
 use crate::chain::counterparty::{channel_connection_client, channel_state_on_destination};
-use crate::chain::handle::ChainHandle;
+use crate::chain::handle::{ChainHandle, Subscription};
 use crate::chain::requests::{
-    IncludeProof, PageRequest, QueryChannelRequest, QueryConnectionChannelsRequest,
-    QueryConnectionRequest, QueryHeight,
+    IncludeProof, PageRequest, Qualified, QueryChannelRequest, QueryClientStateRequest,
+    QueryConnectionChannelsRequest, QueryConnectionRequest, QueryHeight,
+    QueryPacketCommitmentsRequest, QueryPacketEventDataRequest, QueryUnreceivedPacketsRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
+use crate::client_state::AnyClientState;
 use crate::connection::Connection;
-use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
+use crate::error::Error as RelayerError;
+use crate::event::monitor::{EventBatch, Result as MonitorResult};
+use crate::event::IbcEventWithHeight;
+use crate::foreign_client::{ForeignClient, ForeignClientError, HasExpiredOrFrozenError};
+use crate::handshake::HandshakeStepError;
+use crate::light_client::AnyHeader;
+use crate::link::error::LinkError;
+use crate::link::{Link, Resubmit};
 use crate::object::Channel as WorkerChannelObject;
 use crate::supervisor::error::Error as SupervisorError;
-use crate::util::pretty::{PrettyDuration, PrettyOption};
+use crate::telemetry;
+use crate::util::pretty::{PrettyDuration, PrettyOption, PrettySlice};
 use crate::util::retry::retry_with_index;
 use crate::util::retry::RetryResult;
 use crate::util::task::Next;
 
 pub mod error;
+pub mod state;
 pub mod version;
+
+#[cfg(test)]
+mod test_utils;
+
+use error::ChannelErrorDetail;
+use state::HandshakeState;
 use version::Version;
 
 mod handshake_retry {
-    //! Provides utility methods and constants to configure the retry behavior
-    //! for the channel handshake algorithm.
+    //! Provides utility methods to report the failures of the retried steps
+    //! of the channel handshake algorithm. The retry strategy itself is
+    //! configured per chain, see [`RetryPolicy`](crate::config::RetryPolicy).
 
     use crate::channel::ChannelError;
-    use crate::util::retry::{clamp_total, ConstantGrowth};
     use core::time::Duration;
 
-    /// Approximate number of retries per block.
-    const PER_BLOCK_RETRIES: u32 = 10;
-
-    /// Defines the increment in delay between subsequent retries.
-    /// A value of `0` will make the retry delay constant.
-    const DELAY_INCREMENT: u64 = 0;
-
-    /// Maximum retry delay expressed in number of blocks
-    const BLOCK_NUMBER_DELAY: u32 = 10;
-
-    /// The default retry strategy.
-    /// We retry with a constant backoff strategy. The strategy is parametrized by the
-    /// maximum block time expressed as a `Duration`.
-    pub fn default_strategy(max_block_times: Duration) -> impl Iterator<Item = Duration> {
-        let retry_delay = max_block_times / PER_BLOCK_RETRIES;
-
-        clamp_total(
-            ConstantGrowth::new(retry_delay, Duration::from_secs(DELAY_INCREMENT)),
-            retry_delay,
-            max_block_times * BLOCK_NUMBER_DELAY,
-        )
-    }
-
     /// Translates from an error type that the `retry` mechanism threw into
     /// a crate specific error of [`ChannelError`] type.
-    pub fn from_retry_error(e: retry::Error<ChannelError>, description: String) -> ChannelError {
-        ChannelError::max_retry(description, e.tries, e.total_delay, e.error)
+    pub fn from_retry_error(
+        e: retry::Error<ChannelError>,
+        description: String,
+        elapsed: Duration,
+    ) -> ChannelError {
+        ChannelError::max_retry(description, e.tries, e.total_delay, elapsed, e.error)
     }
 }
 
@@ -92,14 +107,19 @@ pub struct ChannelSide<Chain: ChainHandle> {
     version: Option<Version>,
 }
 
+/// Displays a channel end as `chain/client/connection/port/channel`,
+/// with `None` in place of a channel id which is not known yet.
 impl<Chain: ChainHandle> Display for ChannelSide<Chain> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        match (&self.channel_id, &self.version) {
-            (Some(channel_id), Some(version)) => write!(f, "ChannelSide {{ chain: {}, client_id: {}, connection_id: {}, port_id: {}, channel_id: {}, version: {} }}", self.chain, self.client_id, self.connection_id, self.port_id, channel_id, version),
-            (Some(channel_id), None) => write!(f, "ChannelSide {{ chain: {}, client_id: {}, connection_id: {}, port_id: {}, channel_id: {}, version: None }}", self.chain, self.client_id, self.connection_id, self.port_id, channel_id),
-            (None, Some(version)) => write!(f, "ChannelSide {{ chain: {}, client_id: {}, connection_id: {}, port_id: {}, channel_id: None, version: {} }}", self.chain, self.client_id, self.connection_id, self.port_id, version),
-            (None, None) => write!(f, "ChannelSide {{ chain: {}, client_id: {}, connection_id: {}, port_id: {}, channel_id: None, version: None }}", self.chain, self.client_id, self.connection_id, self.port_id),
-        }
+        write!(
+            f,
+            "{}/{}/{}/{}/{}",
+            self.chain.id(),
+            self.client_id,
+            self.connection_id,
+            self.port_id,
+            PrettyOption(&self.channel_id)
+        )
     }
 }
 
@@ -146,6 +166,70 @@ impl<Chain: ChainHandle> ChannelSide<Chain> {
         self.version.as_ref()
     }
 
+    pub fn set_port_id(&mut self, port_id: &PortId) {
+        self.port_id = port_id.clone();
+    }
+
+    pub fn set_channel_id(&mut self, channel_id: &ChannelId) {
+        self.channel_id = Some(channel_id.clone());
+    }
+
+    /// Queries the state of this channel end, `Uninitialized` if its id is not known yet.
+    fn query_state(&self) -> Result<State, ChannelError> {
+        let channel_id = match &self.channel_id {
+            Some(channel_id) => channel_id.clone(),
+            None => return Ok(State::Uninitialized),
+        };
+
+        let (channel_end, _) = self
+            .chain
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.port_id.clone(),
+                    channel_id,
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.chain.id(), e))?;
+
+        Ok(*channel_end.state())
+    }
+
+    /// Whether the given port and channel id designate this channel end, which is
+    /// the case for any channel on the port of this end as long as its id is not known.
+    fn is_end(&self, port_id: &PortId, channel_id: Option<&ChannelId>) -> bool {
+        &self.port_id == port_id
+            && match &self.channel_id {
+                Some(id) => channel_id == Some(id),
+                None => true,
+            }
+    }
+
+    /// Checks that the connection of this side is open on its chain.
+    fn check_connection_open(&self) -> Result<(), ChannelError> {
+        let (connection, _) = self
+            .chain
+            .query_connection(
+                QueryConnectionRequest {
+                    connection_id: self.connection_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::chain_query(self.chain_id(), e))?;
+
+        if !connection.is_open() {
+            return Err(ChannelError::connection_not_open(
+                self.chain_id(),
+                self.connection_id.clone(),
+                *connection.state(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn map_chain<ChainB: ChainHandle>(
         self,
         mapper: impl Fn(Chain) -> ChainB,
@@ -168,18 +252,15 @@ pub struct Channel<ChainA: ChainHandle, ChainB: ChainHandle> {
     pub a_side: ChannelSide<ChainA>,
     pub b_side: ChannelSide<ChainB>,
     pub connection_delay: Duration,
+    /// The time after which the handshakes of this channel give up, if any, in place
+    /// of the [`ChainConfig.handshake_timeout`] of its chains.
+    #[serde(skip)]
+    pub overall_deadline: Option<Instant>,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> Display for Channel<ChainA, ChainB> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        write!(
-            f,
-            "Channel {{ ordering: {}, a_side: {}, b_side: {}, connection_delay: {} }}",
-            self.ordering,
-            self.a_side,
-            self.b_side,
-            PrettyDuration(&self.connection_delay)
-        )
+        write!(f, "{} <-> {} [{}]", self.a_side, self.b_side, self.ordering)
     }
 }
 
@@ -192,6 +273,346 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         a_port: PortId,
         b_port: PortId,
         version: Option<Version>,
+    ) -> Result<Self, ChannelError> {
+        Self::open(connection, ordering, a_port, b_port, version).map(|result| result.channel)
+    }
+
+    /// Same as [`Channel::new`], but returns the channel along with the outcome of its
+    /// handshake, which tells whether the channel was already open or which handshake
+    /// steps were performed.
+    pub fn open(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+    ) -> Result<ChannelHandshakeResult<ChainA, ChainB>, ChannelError> {
+        Self::new_with_progress(connection, ordering, a_port, b_port, version, None)
+    }
+
+    /// Opens a new channel like [`Channel::open`], and calls `on_progress`, if given,
+    /// whenever a step of the channel handshake completes, eg. to report the progress
+    /// of the handshake in a user interface or to record metrics.
+    pub fn new_with_progress(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+        on_progress: Option<&dyn Fn(HandshakeProgress)>,
+    ) -> Result<ChannelHandshakeResult<ChainA, ChainB>, ChannelError> {
+        let mut channel = Self::prepare(connection, ordering, a_port, b_port, version)?;
+
+        let result = channel.open_handshake(
+            HandshakeStep::Init,
+            HandshakeOptions::default(),
+            |_| {},
+            on_progress.unwrap_or(&|_| {}),
+        )?;
+
+        Ok(ChannelHandshakeResult::new(channel, result))
+    }
+
+    /// Opens a channel like [`Channel::open`], unless an open channel with the same
+    /// ports and ordering already exists on the connection, in which case that
+    /// channel is returned rather than opening another one, see
+    /// [`Channel::find_open_channel`].
+    pub fn new_or_reuse(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+    ) -> Result<ChannelHandshakeResult<ChainA, ChainB>, ChannelError> {
+        let mut channel = Self::prepare(connection, ordering, a_port, b_port, version)?;
+
+        let result = match channel.find_open_channel()? {
+            Some((a_channel_id, b_channel_id)) => {
+                info!(
+                    chain = %channel.a_chain().id(),
+                    "reusing open channel {} with counterparty channel {}",
+                    a_channel_id,
+                    b_channel_id
+                );
+
+                channel.a_side.channel_id = Some(a_channel_id);
+                channel.b_side.channel_id = Some(b_channel_id);
+                channel.resume_handshake(HandshakeStep::Open)?
+            }
+            None => channel.open_handshake(
+                HandshakeStep::Init,
+                HandshakeOptions::default(),
+                |_| {},
+                &|_| {},
+            )?,
+        };
+
+        Ok(ChannelHandshakeResult::new(channel, result))
+    }
+
+    /// Restores a channel without channel ids on top of the existing connection, see
+    /// [`Channel::restore`], and checks that it can be opened: the channel must be
+    /// consistent and the connection open.
+    fn prepare(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+    ) -> Result<Self, ChannelError> {
+        let channel = Self::restore(connection, ordering, a_port, b_port, None, None, version)?;
+        channel.validate()?;
+        channel.check_connection_open()?;
+
+        Ok(channel)
+    }
+
+    /// Returns the port and channel ids of all the channels on the given connection,
+    /// as seen from its source chain, so as to relay on every channel of the connection.
+    pub fn discover_channels(
+        connection: &Connection<ChainA, ChainB>,
+    ) -> Result<Vec<(PortId, ChannelId)>, ChannelError> {
+        let connection_id = connection
+            .src_connection_id()
+            .ok_or_else(|| ChannelError::missing_local_connection(connection.src_chain().id()))?;
+
+        connection_channel_ids(&connection.src_chain(), connection_id)
+    }
+
+    /// Opens several channels on top of the same connection, returning the result
+    /// of each of them in the order of `specs`.
+    ///
+    /// Each step of the handshake is performed for all channels at once: the messages
+    /// of the step are submitted to the chain in a single batch, in which the update
+    /// of a client to a given height is included only once. The channels whose step
+    /// fails leave the batch and complete their handshakes on their own, concurrently,
+    /// so that they do not prevent the other channels from being opened.
+    ///
+    /// All the handshakes share the deadline computed when the batch starts, see
+    /// [`Channel::handshake_deadline`]: a channel whose step fails past that deadline
+    /// fails with the error of the step rather than being retried on its own.
+    pub fn new_batch(
+        connection: Connection<ChainA, ChainB>,
+        specs: Vec<ChannelSpec>,
+    ) -> Vec<Result<Self, ChannelError>> {
+        let start = Instant::now();
+
+        let mut channels: Vec<Result<Self, ChannelError>> = specs
+            .into_iter()
+            .map(|spec| {
+                let mut channel = Self::prepare(
+                    connection.clone(),
+                    spec.ordering,
+                    spec.a_port,
+                    spec.b_port,
+                    spec.version,
+                )?;
+                channel.check_clients_active()?;
+                channel.overall_deadline = channel.handshake_deadline(start)?;
+                Ok(channel)
+            })
+            .collect();
+
+        // The channels which left the batch, to be opened on their own
+        let mut unbatched = vec![false; channels.len()];
+
+        for step in [
+            HandshakeStep::Init,
+            HandshakeStep::Try,
+            HandshakeStep::Ack,
+            HandshakeStep::Confirm,
+        ] {
+            let batch: Vec<usize> = (0..channels.len())
+                .filter(|&i| channels[i].is_ok() && !unbatched[i])
+                .collect();
+
+            let results = {
+                let batched: Vec<&Self> = batch
+                    .iter()
+                    .filter_map(|&i| channels[i].as_ref().ok())
+                    .collect();
+
+                match batched.first() {
+                    Some(first) => first.send_batched_step(&batched, step),
+                    None => break,
+                }
+            };
+
+            for (i, result) in batch.into_iter().zip(results) {
+                let channel = match &mut channels[i] {
+                    Ok(channel) => channel,
+                    Err(_) => continue,
+                };
+
+                let recorded = result.and_then(|event| {
+                    let channel_id = extract_channel_id(&event)?;
+                    match step {
+                        HandshakeStep::Init => channel.a_side.set_channel_id(channel_id),
+                        HandshakeStep::Try => channel.b_side.set_channel_id(channel_id),
+                        _ => {}
+                    }
+                    Ok(())
+                });
+
+                if let Err(e) = recorded {
+                    if past_deadline(channel.overall_deadline, Duration::ZERO) {
+                        error!(
+                            "batched {:?} step failed for channel {} past the handshake deadline: {}",
+                            step, channel, e
+                        );
+                        channels[i] = Err(e);
+                        continue;
+                    }
+
+                    warn!(
+                        "batched {:?} step failed for channel {}, completing its handshake on its own: {}",
+                        step, channel, e
+                    );
+                    unbatched[i] = true;
+                }
+            }
+        }
+
+        Self::complete_unbatched(channels, unbatched)
+    }
+
+    /// Completes concurrently the handshakes of the channels which left the batch of
+    /// [`Channel::new_batch`], the other channels being returned as they are.
+    fn complete_unbatched(
+        channels: Vec<Result<Self, ChannelError>>,
+        unbatched: Vec<bool>,
+    ) -> Vec<Result<Self, ChannelError>> {
+        std::thread::scope(|s| {
+            let handshakes: Vec<_> = channels
+                .into_iter()
+                .zip(unbatched)
+                .map(|(channel, unbatched)| match channel {
+                    Ok(mut channel) if unbatched => Either::Right(s.spawn(move || {
+                        channel.handshake()?;
+                        Ok(channel)
+                    })),
+                    channel => Either::Left(channel),
+                })
+                .collect();
+
+            handshakes
+                .into_iter()
+                .map(|handshake| match handshake {
+                    Either::Left(channel) => channel,
+                    Either::Right(handshake) => handshake
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+                })
+                .collect()
+        })
+    }
+
+    /// Builds the messages of the given handshake step for each of the given channels,
+    /// which share the connection of this channel, and submits them in a single batch.
+    /// Returns the event of the step for each channel, in the same order.
+    fn send_batched_step(
+        &self,
+        channels: &[&Self],
+        step: HandshakeStep,
+    ) -> Vec<Result<IbcEvent, ChannelError>> {
+        match step {
+            HandshakeStep::Init => send_batched_msgs(
+                &self.a_chain(),
+                channels
+                    .iter()
+                    .map(|channel| channel.flipped().build_chan_open_init())
+                    .collect(),
+                "ChannelOpenInit",
+                |i, event| {
+                    matches!(event, IbcEvent::OpenInitChannel(_))
+                        && channels[i].is_handshake_event(event, true)
+                },
+            ),
+            HandshakeStep::Try => send_batched_msgs(
+                &self.b_chain(),
+                channels
+                    .iter()
+                    .map(|channel| channel.build_chan_open_try())
+                    .collect(),
+                "ChannelOpenTry",
+                |i, event| {
+                    matches!(event, IbcEvent::OpenTryChannel(_))
+                        && channels[i].is_handshake_event(event, false)
+                },
+            ),
+            HandshakeStep::Ack => send_batched_msgs(
+                &self.a_chain(),
+                channels
+                    .iter()
+                    .map(|channel| channel.flipped().build_chan_open_ack())
+                    .collect(),
+                "ChannelOpenAck",
+                |i, event| {
+                    matches!(event, IbcEvent::OpenAckChannel(_))
+                        && channels[i].is_handshake_event(event, true)
+                },
+            ),
+            HandshakeStep::Confirm | HandshakeStep::Open => send_batched_msgs(
+                &self.b_chain(),
+                channels
+                    .iter()
+                    .map(|channel| channel.build_chan_open_confirm())
+                    .collect(),
+                "ChannelOpenConfirm",
+                |i, event| {
+                    matches!(event, IbcEvent::OpenConfirmChannel(_))
+                        && channels[i].is_handshake_event(event, false)
+                },
+            ),
+        }
+    }
+
+    /// Resumes the handshake of a channel on top of the existing connection, whose
+    /// ends may already exist on either chain, eg. after the relayer stopped half-way
+    /// through a previous handshake.
+    ///
+    /// The handshake continues from the current state of the given channel ends: no
+    /// `ChanOpenInit` is sent if one of the ends exists already, and the handshake
+    /// returns immediately if both ends are open. If only one of the channel ids is
+    /// known, the other one is recovered from the counterparty of the known end.
+    pub fn resume(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        a_channel_id: Option<ChannelId>,
+        b_channel_id: Option<ChannelId>,
+        version: Option<Version>,
+    ) -> Result<Self, ChannelError> {
+        let mut channel = Self::restore(
+            connection,
+            ordering,
+            a_port,
+            b_port,
+            a_channel_id,
+            b_channel_id,
+            version,
+        )?;
+        channel.validate()?;
+
+        channel.resume_handshake(channel.first_handshake_step())?;
+
+        Ok(channel)
+    }
+
+    /// Restores a channel on top of the existing connection, with the given channel
+    /// ids if they are known, without driving the handshake.
+    ///
+    /// The individual handshake steps can then be performed with
+    /// [`Channel::send_chan_open_init`] and the like, eg. from the CLI.
+    pub fn restore(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        a_channel_id: Option<ChannelId>,
+        b_channel_id: Option<ChannelId>,
+        version: Option<Version>,
     ) -> Result<Self, ChannelError> {
         let src_connection_id = connection
             .src_connection_id()
@@ -200,14 +621,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .dst_connection_id()
             .ok_or_else(|| ChannelError::missing_local_connection(connection.dst_chain().id()))?;
 
-        let mut channel = Self {
+        Ok(Self {
             ordering,
             a_side: ChannelSide::new(
                 connection.src_chain(),
                 connection.src_client_id().clone(),
                 src_connection_id.clone(),
                 a_port,
-                Default::default(),
+                a_channel_id,
                 version.clone(),
             ),
             b_side: ChannelSide::new(
@@ -215,17 +636,141 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
                 connection.dst_client_id().clone(),
                 dst_connection_id.clone(),
                 b_port,
-                Default::default(),
+                b_channel_id,
                 version,
             ),
             connection_delay: connection.delay_period,
-        };
+            overall_deadline: None,
+        })
+    }
+
+    /// Restores a channel whose ends are already open on top of the existing connection,
+    /// eg. a channel opened by another tool, without running the handshake nor submitting
+    /// anything to the chains.
+    ///
+    /// The ordering and version of the channel are the ones of the end on chain a, and
+    /// both ends are checked to be open and to agree with each other, see
+    /// [`Channel::verify`] for the errors returned when they do not.
+    pub fn restore_from_existing(
+        connection: Connection<ChainA, ChainB>,
+        a_port: PortId,
+        a_channel_id: ChannelId,
+        b_port: PortId,
+        b_channel_id: ChannelId,
+    ) -> Result<Self, ChannelError> {
+        let mut channel = Self::restore(
+            connection,
+            Order::None,
+            a_port,
+            b_port,
+            Some(a_channel_id.clone()),
+            Some(b_channel_id),
+            None,
+        )?;
+
+        let a_height = channel
+            .a_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::chain_query(channel.a_chain().id(), e))?;
+        let a_channel = channel.a_channel(Some(&a_channel_id), a_height)?;
+
+        channel.ordering = *a_channel.ordering();
+        channel.a_side.version = Some(a_channel.version().clone());
+        channel.b_side.version = Some(a_channel.version().clone());
+
+        channel.validate()?;
+        channel.verify()?;
+
+        Ok(channel)
+    }
+
+    /// Same as [`Channel::new`], but does not submit anything to the chains: returns
+    /// the messages which the first step of the handshake would submit instead, so that
+    /// they can be reviewed beforehand.
+    ///
+    /// The messages are the ones which the handshake submits, client updates included.
+    /// Only the first step is returned since the messages of the next steps depend on
+    /// the channel end created by the previous one.
+    pub fn dry_run(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+    ) -> Result<HandshakeMessages, ChannelError> {
+        let mut channel = Self::prepare(connection, ordering, a_port, b_port, version)?;
+
+        channel.build_chan_open_messages()
+    }
+
+    /// Same as [`Channel::new`], but persists the handshake state to the given file
+    /// after every handshake step, so that the handshake can be resumed from the
+    /// channel ends which were already created if the relayer stops half-way through.
+    ///
+    /// If the file holds the state of a previous handshake between the same chains,
+    /// connections and ports, that handshake is resumed instead of starting a new one.
+    pub fn new_with_state_file(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Order,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+        state_file: &Path,
+    ) -> Result<Self, ChannelError> {
+        let mut channel = Self::prepare(connection, ordering, a_port, b_port, version)?;
+
+        if channel.load_state(state_file)? {
+            info!(
+                "resuming channel handshake from state file '{}'",
+                state_file.display()
+            );
+        }
 
-        channel.handshake()?;
+        let from = channel.first_handshake_step();
+        channel.open_handshake(
+            from,
+            HandshakeOptions::default(),
+            |channel| {
+                // Persist the channel ids assigned so far, even if the step failed
+                if let Err(e) = channel.save_state(state_file) {
+                    warn!("failed to persist the channel handshake state: {}", e);
+                }
+            },
+            &|_| {},
+        )?;
 
         Ok(channel)
     }
 
+    /// Stores the handshake state of this channel to the given file: the chains,
+    /// connections and ports of its ends, along with the channel ids assigned so far.
+    pub fn save_state(&self, path: &Path) -> Result<(), ChannelError> {
+        HandshakeState::from_channel(self).store(path)
+    }
+
+    /// Resumes the handshake state stored to the given file by [`Channel::save_state`],
+    /// by assigning its channel ids to this channel. The state is ignored, with a
+    /// warning, if it was stored for other chains, connections or ports.
+    ///
+    /// Returns whether the state was resumed.
+    pub fn load_state(&mut self, path: &Path) -> Result<bool, ChannelError> {
+        match HandshakeState::load(path)? {
+            Some(state) if state.matches(self) => {
+                self.a_side.channel_id = state.a_side.channel_id;
+                self.b_side.channel_id = state.b_side.channel_id;
+                Ok(true)
+            }
+            Some(_) => {
+                warn!(
+                    "ignoring state file '{}' which belongs to another channel handshake",
+                    path.display()
+                );
+                Ok(false)
+            }
+            None => Ok(false),
+        }
+    }
+
     pub fn restore_from_event(
         chain: ChainA,
         counterparty_chain: ChainB,
@@ -256,11 +801,27 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .connection_id()
             .ok_or_else(ChannelError::missing_counterparty_connection)?;
 
+        // The event does not include the channel ordering, retrieve it from the channel end.
+        let ordering = match &channel_id {
+            Some(channel_id) => {
+                let (channel_end, _) = chain
+                    .query_channel(
+                        QueryChannelRequest {
+                            port_id: port_id.clone(),
+                            channel_id: channel_id.clone(),
+                            height: QueryHeight::Latest,
+                        },
+                        IncludeProof::No,
+                    )
+                    .map_err(ChannelError::relayer)?;
+
+                *channel_end.ordering()
+            }
+            None => Order::default(),
+        };
+
         Ok(Channel {
-            // The event does not include the channel ordering.
-            // The message handlers `build_chan_open..` determine the order included in the handshake
-            // message from channel query.
-            ordering: Default::default(),
+            ordering,
             a_side: ChannelSide::new(
                 chain,
                 connection.client_id().clone(),
@@ -280,9 +841,34 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
                 None,
             ),
             connection_delay: connection.delay_period(),
+            overall_deadline: None,
         })
     }
 
+    /// Recreates a channel from its end on `chain`, given by its port and channel ids,
+    /// as of the latest height of the chain. The ports, connections and clients of both
+    /// ends, and the channel id of the counterparty end if it exists, are looked up on
+    /// the chains, see [`Channel::restore_from_state`].
+    pub fn restore_from_chain(
+        chain: ChainA,
+        counterparty_chain: ChainB,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(Channel<ChainA, ChainB>, State), ChannelError> {
+        let height = chain
+            .query_latest_height()
+            .map_err(|e| ChannelError::chain_query(chain.id(), e))?;
+
+        let channel = WorkerChannelObject {
+            dst_chain_id: counterparty_chain.id(),
+            src_chain_id: chain.id(),
+            src_channel_id: channel_id,
+            src_port_id: port_id,
+        };
+
+        Self::restore_from_state(chain, counterparty_chain, channel, height)
+    }
+
     /// Recreates a 'Channel' object from the worker's object built from chain state scanning.
     /// The channel must exist on chain and its connection must be initialized on both chains.
     pub fn restore_from_state(
@@ -350,6 +936,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
                 None,
             ),
             connection_delay: a_connection.delay_period(),
+            overall_deadline: None,
         };
 
         if a_channel.state_matches(&State::Init) && a_channel.remote.channel_id.is_none() {
@@ -363,7 +950,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             for chan in channels {
                 if let Some(remote_channel_id) = chan.channel_end.remote.channel_id() {
                     if remote_channel_id == &channel.src_channel_id {
-                        handshake_channel.b_side.channel_id = Some(chan.channel_id);
+                        handshake_channel.b_side.set_channel_id(&chan.channel_id);
                         break;
                     }
                 }
@@ -421,6 +1008,20 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         self.b_side.channel_id()
     }
 
+    /// Returns the source channel identifier, or a
+    /// [`ChannelError::missing_local_channel_id`] error if it has not been assigned yet.
+    pub fn require_src_channel_id(&self) -> Result<&ChannelId, ChannelError> {
+        self.src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)
+    }
+
+    /// Returns the destination channel identifier, or a
+    /// [`ChannelError::missing_counterparty_channel_id`] error if it has not been assigned yet.
+    pub fn require_dst_channel_id(&self) -> Result<&ChannelId, ChannelError> {
+        self.dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)
+    }
+
     pub fn a_channel_id(&self) -> Option<&ChannelId> {
         self.a_side.channel_id()
     }
@@ -437,14 +1038,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         self.b_side.version.as_ref()
     }
 
-    fn a_channel(&self, channel_id: Option<&ChannelId>) -> Result<ChannelEnd, ChannelError> {
+    fn a_channel(
+        &self,
+        channel_id: Option<&ChannelId>,
+        height: Height,
+    ) -> Result<ChannelEnd, ChannelError> {
         if let Some(id) = channel_id {
             self.a_chain()
                 .query_channel(
                     QueryChannelRequest {
                         port_id: self.a_side.port_id.clone(),
                         channel_id: id.clone(),
-                        height: QueryHeight::Latest,
+                        height: QueryHeight::Specific(height),
                     },
                     IncludeProof::No,
                 )
@@ -455,14 +1060,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
-    fn b_channel(&self, channel_id: Option<&ChannelId>) -> Result<ChannelEnd, ChannelError> {
+    fn b_channel(
+        &self,
+        channel_id: Option<&ChannelId>,
+        height: Height,
+    ) -> Result<ChannelEnd, ChannelError> {
         if let Some(id) = channel_id {
             self.b_chain()
                 .query_channel(
                     QueryChannelRequest {
                         port_id: self.b_side.port_id.clone(),
                         channel_id: id.clone(),
-                        height: QueryHeight::Latest,
+                        height: QueryHeight::Specific(height),
                     },
                     IncludeProof::No,
                 )
@@ -473,35 +1082,49 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
-    /// Returns a `Duration` representing the maximum value among the
-    /// [`ChainConfig.max_block_time`] for the two networks that
-    /// this channel belongs to.
-    fn max_block_times(&self) -> Result<Duration, ChannelError> {
-        let a_block_time = self
-            .a_chain()
-            .config()
-            .map_err(ChannelError::relayer)?
-            .max_block_time;
-        let b_block_time = self
-            .b_chain()
-            .config()
-            .map_err(ChannelError::relayer)?
-            .max_block_time;
-        Ok(a_block_time.max(b_block_time))
+    /// Returns the retry strategy for the handshake steps, built from the maximum
+    /// [`ChainConfig.max_block_time`] and the most lenient [`ChainConfig.handshake_retry`]
+    /// policy among the two networks that this channel belongs to.
+    fn handshake_retry_strategy(&self) -> Result<impl Iterator<Item = Duration>, ChannelError> {
+        let a_config = self.a_chain().config().map_err(ChannelError::relayer)?;
+        let b_config = self.b_chain().config().map_err(ChannelError::relayer)?;
+
+        let max_block_times = a_config.max_block_time.max(b_config.max_block_time);
+        let policy = a_config.handshake_retry.max(b_config.handshake_retry);
+
+        Ok(policy.strategy(max_block_times))
+    }
+
+    /// Returns the time after which a handshake starting at `start` gives up: the
+    /// [`Channel::overall_deadline`] if set, otherwise after the longest of the
+    /// [`ChainConfig.handshake_timeout`] of the two chains.
+    fn handshake_deadline(&self, start: Instant) -> Result<Option<Instant>, ChannelError> {
+        if let Some(deadline) = self.overall_deadline {
+            return Ok(Some(deadline));
+        }
+
+        let a_config = self.a_chain().config().map_err(ChannelError::relayer)?;
+        let b_config = self.b_chain().config().map_err(ChannelError::relayer)?;
+
+        Ok(start.checked_add(a_config.handshake_timeout.max(b_config.handshake_timeout)))
     }
 
+    /// Returns the same channel as seen from the other chain: the two sides are
+    /// swapped, while the ordering, the connection delay and the overall deadline, which
+    /// do not depend on the orientation, are kept. Flipping twice gives back an identical channel.
     pub fn flipped(&self) -> Channel<ChainB, ChainA> {
         Channel {
             ordering: self.ordering,
             a_side: self.b_side.clone(),
             b_side: self.a_side.clone(),
             connection_delay: self.connection_delay,
+            overall_deadline: self.overall_deadline,
         }
     }
 
     /// Queries the chains for latest channel end information. It verifies the relayer channel
     /// IDs and updates them if needed.
-    /// Returns the states of the two channel ends.
+    /// Returns the two channel ends.
     ///
     /// The relayer channel stores the channel identifiers on the two chains a and b.
     /// These identifiers need to be cross validated with the corresponding on-chain ones at some
@@ -549,11 +1172,61 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     /// Here relayer r1 has a_side channel 1 and b_side is unknown
     /// while on chain a the counterparty of channel 1 is 100. r1 needs to update
     /// its b_side to 100
-    fn update_channel_and_query_states(&mut self) -> Result<(State, State), ChannelError> {
-        let relayer_a_id = self.a_side.channel_id();
+    ///
+    /// Both channel ends are queried at the latest height of their chain as of the
+    /// start of the query, so that the pair of states is read from a stable snapshot
+    /// rather than straddling a block boundary. Returns those heights along with
+    /// the channel ends.
+    fn update_channel_and_query_ends(&mut self) -> Result<ChannelEnds, ChannelError> {
+        let start = Instant::now();
+        let (a_height, b_height) = std::thread::scope(|s| {
+            let a_height = s.spawn(|| self.a_chain().query_latest_height());
+            let b_height = self.b_chain().query_latest_height();
+            (join_query(a_height), b_height)
+        });
+        let a_height = a_height.map_err(|e| ChannelError::chain_query(self.a_chain().id(), e))?;
+        let b_height = b_height.map_err(|e| ChannelError::chain_query(self.b_chain().id(), e))?;
+
+        let relayer_a_id = self.a_side.channel_id().cloned();
         let relayer_b_id = self.b_side.channel_id().cloned();
 
-        let a_channel = self.a_channel(relayer_a_id)?;
+        // The two ends are queried concurrently with the ids known to the relayer.
+        // The end on chain b is queried again below if the end on chain a reports
+        // a different counterparty.
+        let (a_channel, b_channel) = std::thread::scope(|s| {
+            let a_channel = s.spawn(|| self.a_channel(relayer_a_id.as_ref(), a_height));
+            let b_channel = self.b_channel(relayer_b_id.as_ref(), b_height);
+            (join_query(a_channel), b_channel)
+        });
+
+        debug!(
+            "queried the channel ends on {} and {} in {}",
+            self.a_chain().id(),
+            self.b_chain().id(),
+            PrettyDuration(&start.elapsed())
+        );
+
+        // Report the state of the end which could be queried when the other one could not
+        match (&a_channel, &b_channel) {
+            (Err(e), Ok(b_channel)) => warn!(
+                "failed to query the channel end on {}, the end on {} is {}: {}",
+                self.a_chain().id(),
+                self.b_chain().id(),
+                b_channel.state(),
+                e
+            ),
+            (Ok(a_channel), Err(e)) => warn!(
+                "failed to query the channel end on {}, the end on {} is {}: {}",
+                self.b_chain().id(),
+                self.a_chain().id(),
+                a_channel.state(),
+                e
+            ),
+            _ => {}
+        }
+
+        let a_channel = a_channel?;
+        let relayer_a_id = relayer_a_id.as_ref();
         let a_counterparty_id = a_channel.counterparty().channel_id();
 
         if a_counterparty_id.is_some() && a_counterparty_id != relayer_b_id.as_ref() {
@@ -571,7 +1244,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
 
         let updated_relayer_b_id = self.b_side.channel_id();
-        let b_channel = self.b_channel(updated_relayer_b_id)?;
+        let b_channel = if updated_relayer_b_id == relayer_b_id.as_ref() {
+            b_channel?
+        } else {
+            self.b_channel(updated_relayer_b_id, b_height)?
+        };
         let b_counterparty_id = b_channel.counterparty().channel_id();
 
         if b_counterparty_id.is_some() && b_counterparty_id != relayer_a_id {
@@ -588,105 +1265,747 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
                 );
                 self.a_side.channel_id = b_counterparty_id.cloned();
             } else {
-                panic!(
-                    "mismatched channel ids in channel ends: {} - {} and {} - {}",
-                    self.a_chain().id(),
-                    a_channel,
+                // The end on chain b was just updated to the counterparty of the end
+                // on chain a, which does not point back to the end on chain a
+                return Err(ChannelError::mismatch_channel_ends(
                     self.b_chain().id(),
-                    b_channel,
-                );
+                    PortChannelId::new(
+                        updated_relayer_b_id.cloned().unwrap_or_default(),
+                        self.b_side.port_id().clone(),
+                    ),
+                    PortChannelId::new(
+                        relayer_a_id.cloned().unwrap_or_default(),
+                        self.a_side.port_id().clone(),
+                    ),
+                    PortChannelId::new(
+                        b_counterparty_id.cloned().unwrap_or_default(),
+                        b_channel.counterparty().port_id().clone(),
+                    ),
+                ));
             }
         }
-        Ok((*a_channel.state(), *b_channel.state()))
+        Ok(ChannelEnds {
+            a_channel,
+            a_height,
+            b_channel,
+            b_height,
+        })
     }
 
-    /// Sends a channel open handshake message.
-    /// The message sent depends on the chain status of the channel ends.
-    fn do_chan_open_handshake(&mut self) -> Result<(), ChannelError> {
-        let (a_state, b_state) = self.update_channel_and_query_states()?;
+    /// Checks that a channel end, if it exists already, has the ordering of this channel.
+    fn check_ordering(
+        &self,
+        chain_id: ChainId,
+        channel_id: Option<&ChannelId>,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), ChannelError> {
+        match channel_id {
+            Some(channel_id)
+                if !channel_end.state_matches(&State::Uninitialized)
+                    && channel_end.ordering() != &self.ordering =>
+            {
+                Err(ChannelError::mismatch_ordering(
+                    chain_id,
+                    channel_id.clone(),
+                    self.ordering,
+                    *channel_end.ordering(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Sends a channel open handshake message.
+    /// The message sent depends on the chain status of the channel ends.
+    /// Performs the next step of the channel opening handshake, and records the
+    /// step reached, the attempt and the event of the submitted message in `progress`.
+    /// `on_progress` is called once the message is committed, or the handshake completed.
+    fn do_chan_open_handshake(
+        &mut self,
+        from: HandshakeStep,
+        progress: &mut HandshakeResult,
+        on_progress: &dyn Fn(HandshakeProgress),
+    ) -> Result<(), ChannelError> {
+        let ChannelEnds {
+            a_channel,
+            a_height,
+            b_channel,
+            b_height,
+        } = self.update_channel_and_query_ends()?;
+
+        // Channel ends which already exist must have the requested ordering
+        self.check_ordering(self.a_chain().id(), self.a_channel_id(), &a_channel)?;
+        self.check_ordering(self.b_chain().id(), self.b_channel_id(), &b_channel)?;
+
+        let (a_state, b_state) = (*a_channel.state(), *b_channel.state());
+        progress.states = Some((a_state, b_state));
         debug!(
-            "do_chan_open_handshake with channel end states: {}, {}",
-            a_state, b_state
+            "do_chan_open_handshake with channel end states: {} at height {} on {}, {} at height {} on {}",
+            a_state,
+            a_height,
+            self.a_chain().id(),
+            b_state,
+            b_height,
+            self.b_chain().id(),
         );
 
-        match (a_state, b_state) {
-            // send the Init message to chain a (source)
-            (State::Uninitialized, State::Uninitialized) => {
-                let event = self
-                    .flipped()
-                    .build_chan_open_init_and_send()
-                    .map_err(|e| {
-                        error!("failed ChanOpenInit {}: {}", self.a_side, e);
-                        e
-                    })?;
-                let channel_id = extract_channel_id(&event)?;
-                self.a_side.channel_id = Some(channel_id.clone());
+        // The phases before `from` are known to be completed, if the chain states do not
+        // reflect it yet the node is lagging behind: retry instead of sending them again.
+        if let Some(step) = HandshakeStep::from_states(a_state, b_state) {
+            progress.step = step;
+            *progress.attempts.entry(step).or_default() += 1;
+
+            if step < from {
+                warn!(
+                    "channel end states {}, {} are behind the expected handshake step {:?}, \
+                    will retry to account for RPC node data availability issues",
+                    a_state, b_state, from
+                );
+
+                return Err(ChannelError::handshake_finalize());
+            }
+        }
+
+        match HandshakeStep::from_states(a_state, b_state) {
+            Some(HandshakeStep::Open) => {
+                info!("channel handshake already finished for {}", self);
+                progress.already_open = progress.steps.is_empty();
+                on_progress(HandshakeProgress {
+                    step: HandshakeStep::Open,
+                    chain_id: self.a_chain().id(),
+                    channel_id: self.a_channel_id().cloned(),
+                    height: a_height,
+                });
+                return Ok(());
             }
 
-            // send the Try message to chain a (source)
-            (State::Uninitialized, State::Init) | (State::Init, State::Init) => {
-                let event = self.flipped().build_chan_open_try_and_send().map_err(|e| {
-                    error!("failed ChanOpenTry {}: {}", self.a_side, e);
+            Some(step) => {
+                let destination = step.destination(a_state, b_state);
+                let result = self.submit_step(step, destination).map_err(|e| {
+                    match destination {
+                        ChannelEndSide::A => error!("failed {} {}: {}", step, self.a_side, e),
+                        ChannelEndSide::B => error!("failed {} {}: {}", step, self.b_side, e),
+                    }
                     e
                 })?;
 
-                let channel_id = extract_channel_id(&event)?;
-                self.a_side.channel_id = Some(channel_id.clone());
+                // The Init and Try steps create the channel end on the destination
+                if matches!(step, HandshakeStep::Init | HandshakeStep::Try) {
+                    let channel_id = extract_channel_id(&result.event)?;
+                    match destination {
+                        ChannelEndSide::A => self.a_side.set_channel_id(channel_id),
+                        ChannelEndSide::B => self.b_side.set_channel_id(channel_id),
+                    }
+                }
+
+                let chain_id = match destination {
+                    ChannelEndSide::A => self.a_chain().id(),
+                    ChannelEndSide::B => self.b_chain().id(),
+                };
+                on_progress(HandshakeProgress::new(step, chain_id, &result));
+                progress.record(result.event);
             }
 
-            // send the Try message to chain b (destination)
-            (State::Init, State::Uninitialized) => {
-                let event = self.build_chan_open_try_and_send().map_err(|e| {
-                    error!("failed ChanOpenTry {}: {}", self.b_side, e);
-                    e
-                })?;
+            None => {
+                warn!(
+                    "do_conn_open_handshake does not handle channel end state combination: \
+                    {}-{}, {}-{}. will retry to account for RPC node data availability issues.",
+                    self.a_chain().id(),
+                    a_state,
+                    self.b_chain().id(),
+                    b_state
+                );
+            }
+        }
+        Err(ChannelError::handshake_finalize())
+    }
 
-                let channel_id = extract_channel_id(&event)?;
-                self.b_side.channel_id = Some(channel_id.clone());
+    /// Builds and sends the message of the given handshake step to the chain on the
+    /// `destination` side, as oriented by [`HandshakeStep::destination`].
+    fn submit_step(
+        &self,
+        step: HandshakeStep,
+        destination: ChannelEndSide,
+    ) -> Result<IbcEventWithHeight, ChannelError> {
+        match destination {
+            ChannelEndSide::A => self
+                .flipped()
+                .record_handshake_step(step, |channel| channel.submit_step_to_dst(step)),
+            ChannelEndSide::B => {
+                self.record_handshake_step(step, |channel| channel.submit_step_to_dst(step))
             }
+        }
+    }
 
-            // send the Ack message to chain a (source)
-            (State::Init, State::TryOpen) | (State::TryOpen, State::TryOpen) => {
-                self.flipped().build_chan_open_ack_and_send().map_err(|e| {
-                    error!("failed ChanOpenAck {}: {}", self.a_side, e);
-                    e
-                })?;
+    /// Builds the messages of the given handshake step for the chain on the
+    /// `destination` side, as oriented by [`HandshakeStep::destination`].
+    fn build_step(
+        &self,
+        step: HandshakeStep,
+        destination: ChannelEndSide,
+    ) -> Result<Vec<Any>, ChannelError> {
+        match destination {
+            ChannelEndSide::A => self.flipped().build_step_for_dst(step),
+            ChannelEndSide::B => self.build_step_for_dst(step),
+        }
+    }
+
+    fn submit_step_to_dst(&self, step: HandshakeStep) -> Result<IbcEventWithHeight, ChannelError> {
+        match step {
+            HandshakeStep::Init => self.submit_chan_open_init(),
+            HandshakeStep::Try => self.submit_chan_open_try(),
+            HandshakeStep::Ack => self.submit_chan_open_ack(),
+            HandshakeStep::Confirm => self.submit_chan_open_confirm(),
+            HandshakeStep::Open => Err(ChannelError::invalid_channel(
+                "the channel is already open, there is no message to send".to_string(),
+            )),
+        }
+    }
+
+    fn build_step_for_dst(&self, step: HandshakeStep) -> Result<Vec<Any>, ChannelError> {
+        match step {
+            HandshakeStep::Init => self.build_chan_open_init(),
+            HandshakeStep::Try => self.build_chan_open_try(),
+            HandshakeStep::Ack => self.build_chan_open_ack(),
+            HandshakeStep::Confirm => self.build_chan_open_confirm(),
+            HandshakeStep::Open => Ok(vec![]),
+        }
+    }
+
+    /// Builds the messages of the next step of the channel opening handshake, as far
+    /// as the current state of the channel ends allows, without submitting them.
+    /// The messages include the client updates which the step requires.
+    pub fn build_chan_open_messages(&mut self) -> Result<HandshakeMessages, ChannelError> {
+        let ChannelEnds {
+            a_channel,
+            b_channel,
+            ..
+        } = self.update_channel_and_query_ends()?;
+        let (a_state, b_state) = (*a_channel.state(), *b_channel.state());
+
+        let step = HandshakeStep::from_states(a_state, b_state).ok_or_else(|| {
+            ChannelError::invalid_channel(format!(
+                "cannot build handshake messages for channel end states {a_state}, {b_state}"
+            ))
+        })?;
+
+        let destination = step.destination(a_state, b_state);
+        let chain_id = match destination {
+            ChannelEndSide::A => self.a_chain().id(),
+            ChannelEndSide::B => self.b_chain().id(),
+        };
+        let messages = self.build_step(step, destination)?;
+
+        Ok(HandshakeMessages {
+            step,
+            chain_id,
+            messages,
+        })
+    }
+
+    /// Checks that the connection of this channel is open on both chains, so that
+    /// the handshake fails right away rather than after retrying steps which cannot
+    /// succeed on top of an unusable connection.
+    pub fn check_connection_open(&self) -> Result<(), ChannelError> {
+        let (a_result, b_result) = std::thread::scope(|s| {
+            let a_result = s.spawn(|| self.a_side.check_connection_open());
+            let b_result = self.b_side.check_connection_open();
+            (join_query(a_result), b_result)
+        });
+
+        a_result?;
+        b_result
+    }
+
+    /// Checks that the clients underlying the connection of this channel are neither
+    /// frozen nor expired, so that the handshake fails right away rather than after
+    /// retrying steps whose proofs can never be verified. A client whose state cannot
+    /// be checked is only reported, the handshake steps then surface the actual error.
+    pub fn check_clients_active(&self) -> Result<(), ChannelError> {
+        let a_client = ForeignClient::restore(
+            self.a_side.client_id().clone(),
+            self.a_chain(),
+            self.b_chain(),
+        );
+        let b_client = ForeignClient::restore(
+            self.b_side.client_id().clone(),
+            self.b_chain(),
+            self.a_chain(),
+        );
+
+        let (a_result, b_result) = std::thread::scope(|s| {
+            let a_result = s.spawn(|| a_client.validated_client_state());
+            let b_result = b_client.validated_client_state();
+            (join_query(a_result), b_result)
+        });
+
+        check_client_active(&a_client, a_result)?;
+        check_client_active(&b_client, b_result)
+    }
+
+    /// Checks the consistency of this channel before anything is queried or submitted:
+    /// the two ends must be on different chains and have a port, and the ordering
+    /// must be either ordered or unordered.
+    pub fn validate(&self) -> Result<(), ChannelError> {
+        if self.a_side.chain_id() == self.b_side.chain_id() {
+            return Err(ChannelError::invalid_config(format!(
+                "both ends of the channel are on chain '{}'",
+                self.a_side.chain_id()
+            )));
+        }
+
+        for port_id in [&self.a_side.port_id, &self.b_side.port_id] {
+            if port_id.as_str().is_empty() {
+                return Err(ChannelError::invalid_config(
+                    "the port of a channel end is empty".to_string(),
+                ));
             }
+        }
 
-            // send the Ack message to chain b (destination)
-            (State::TryOpen, State::Init) => {
-                self.build_chan_open_ack_and_send().map_err(|e| {
-                    error!("failed ChanOpenAck {}: {}", self.b_side, e);
-                    e
-                })?;
+        if self.ordering == Order::None {
+            return Err(ChannelError::invalid_config(format!(
+                "invalid channel ordering '{}', expected '{}' or '{}'",
+                self.ordering,
+                Order::Ordered,
+                Order::Unordered
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that both ends of this channel are open and agree with each other and
+    /// with this channel: same ordering, the connection of each side as the only
+    /// connection hop, each end having the other one as its counterparty and, if
+    /// requested, the expected version. Nothing is submitted to the chains.
+    ///
+    /// Returns the error of the first check which failed.
+    pub fn verify(&self) -> Result<(), ChannelError> {
+        let a_channel_id = self
+            .a_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let b_channel_id = self
+            .b_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let a_height = self
+            .a_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::chain_query(self.a_chain().id(), e))?;
+        let b_height = self
+            .b_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::chain_query(self.b_chain().id(), e))?;
+
+        let a_channel = self.a_channel(Some(a_channel_id), a_height)?;
+        let b_channel = self.b_channel(Some(b_channel_id), b_height)?;
+
+        verify_channel_end(
+            self.a_chain().id(),
+            PortChannelId::new(a_channel_id.clone(), self.a_side.port_id.clone()),
+            &a_channel,
+            &ChannelEnd::new(
+                State::Open,
+                self.ordering,
+                Counterparty::new(self.b_side.port_id.clone(), Some(b_channel_id.clone())),
+                vec![self.a_side.connection_id.clone()],
+                self.a_side.version.clone().unwrap_or_else(Version::empty),
+            ),
+        )?;
+
+        verify_channel_end(
+            self.b_chain().id(),
+            PortChannelId::new(b_channel_id.clone(), self.b_side.port_id.clone()),
+            &b_channel,
+            &ChannelEnd::new(
+                State::Open,
+                self.ordering,
+                Counterparty::new(self.a_side.port_id.clone(), Some(a_channel_id.clone())),
+                vec![self.b_side.connection_id.clone()],
+                self.b_side.version.clone().unwrap_or_else(Version::empty),
+            ),
+        )
+    }
+
+    /// Queries the state of the two channel ends, without updating this channel nor
+    /// submitting anything. An end whose channel id is not known yet is reported as
+    /// `Uninitialized`.
+    pub fn query_status(&self) -> Result<(State, State), ChannelError> {
+        let (a_state, b_state) = std::thread::scope(|s| {
+            let a_state = s.spawn(|| self.a_side.query_state());
+            let b_state = self.b_side.query_state();
+            (join_query(a_state), b_state)
+        });
+
+        Ok((a_state?, b_state?))
+    }
+
+    /// Returns the first handshake step which may still be needed given the known
+    /// channel ids: a known channel id means that `ChanOpenInit` was sent already,
+    /// so the handshake must never start over with a new channel.
+    pub fn first_handshake_step(&self) -> HandshakeStep {
+        if self.a_side.channel_id.is_some() || self.b_side.channel_id.is_some() {
+            HandshakeStep::Try
+        } else {
+            HandshakeStep::Init
+        }
+    }
+
+    /// Executes the channel handshake protocol (ICS004).
+    /// Returns the channel ids and the events of the messages which were submitted.
+    pub fn handshake(&mut self) -> Result<HandshakeResult, ChannelError> {
+        self.resume_handshake(HandshakeStep::Init)
+    }
+
+    /// Executes the channel handshake protocol (ICS004), starting from the given step.
+    ///
+    /// The phases before `from` are assumed to be completed already: as long as the
+    /// channel ends do not reflect them, the relayer waits for the chains to catch up
+    /// instead of sending the corresponding messages again. Resuming from
+    /// [`HandshakeStep::Open`] only checks that both ends are open, see [`Channel::verify`].
+    pub fn resume_handshake(
+        &mut self,
+        from: HandshakeStep,
+    ) -> Result<HandshakeResult, ChannelError> {
+        if from == HandshakeStep::Open {
+            // Only report the handshake as complete once both ends are seen open
+            self.verify()?;
+
+            let mut result = HandshakeResult::new(self, HandshakeStep::Open);
+            result.already_open = true;
+            return Ok(result);
+        }
+
+        self.open_handshake(from, HandshakeOptions::default(), |_| {}, &|_| {})
+    }
+
+    /// Performs a single action of the channel opening handshake, without retrying nor
+    /// waiting: the channel ends are queried and the message of the next step, if any,
+    /// is submitted.
+    ///
+    /// The caller drives the handshake by calling this method again, right away after
+    /// [`ChannelStep::Pending`] and after a delay of its choosing after
+    /// [`ChannelStep::NeedsRetry`], until [`ChannelStep::Done`]. Errors which retrying
+    /// cannot fix, eg. a frozen client, are returned as such.
+    pub fn step(&mut self) -> Result<ChannelStep, ChannelError> {
+        let from = self.first_handshake_step();
+        let mut progress = HandshakeResult::new(self, from);
+
+        let error = match self.do_chan_open_handshake(from, &mut progress, &|_| {}) {
+            Ok(()) => return Ok(ChannelStep::Done),
+            Err(e) => e,
+        };
+
+        match (progress.steps.pop(), progress.events.pop()) {
+            (Some(step), Some(event)) => Ok(ChannelStep::Pending {
+                step,
+                event: Box::new(event),
+            }),
+            _ if error.is_expired_or_frozen_error()
+                || error.is_read_only_mode_error()
+                || !error.is_retryable() =>
+            {
+                Err(error)
+            }
+            _ => Ok(ChannelStep::NeedsRetry(Box::new(error))),
+        }
+    }
+
+    /// Executes the channel handshake protocol (ICS004) like [`Channel::handshake`], but
+    /// driven by the channel handshake events of both chains: after a step which could
+    /// not be performed yet, the channel ends are queried again as soon as an event of
+    /// this channel is received, or after the retry delay if none arrives.
+    ///
+    /// This spares the RPC nodes the queries of the channel ends which would otherwise
+    /// be performed after every retry delay, while the number of attempts, the delays
+    /// and the deadline remain those of [`Channel::handshake`].
+    pub fn handshake_with_events(&mut self) -> Result<HandshakeResult, ChannelError> {
+        let options = HandshakeOptions {
+            events: Some(HandshakeEvents {
+                a: self.a_chain().subscribe().map_err(ChannelError::relayer)?,
+                b: self.b_chain().subscribe().map_err(ChannelError::relayer)?,
+            }),
+            ..HandshakeOptions::default()
+        };
+
+        self.open_handshake(HandshakeStep::Init, options, |_| {}, &|_| {})
+    }
+
+    /// Whether an event of chain a or b, according to `on_a`, concerns this channel:
+    /// it is a channel handshake event of the end on that chain, whose counterparty is
+    /// the port of the other end.
+    fn is_handshake_event(&self, event: &IbcEvent, on_a: bool) -> bool {
+        let attributes = match event.clone().channel_attributes() {
+            Some(attributes) => attributes,
+            None => return false,
+        };
+
+        let channel_id = attributes.channel_id.as_ref();
+
+        if on_a {
+            self.a_side.is_end(&attributes.port_id, channel_id)
+                && attributes.counterparty_port_id == self.b_side.port_id
+        } else {
+            self.b_side.is_end(&attributes.port_id, channel_id)
+                && attributes.counterparty_port_id == self.a_side.port_id
+        }
+    }
+
+    /// Repeatedly performs the steps of the channel opening handshake, starting from
+    /// the given step and until the deadline, if any, and calls `after_step` after
+    /// every attempt. `on_progress` is called whenever a step completes.
+    #[instrument(
+        name = "channel.open_handshake",
+        level = "error",
+        skip_all,
+        fields(
+            src_chain = %self.a_chain().id(),
+            dst_chain = %self.b_chain().id(),
+            src_port = %self.a_side.port_id(),
+            dst_port = %self.b_side.port_id(),
+        )
+    )]
+    fn open_handshake(
+        &mut self,
+        from: HandshakeStep,
+        options: HandshakeOptions,
+        mut after_step: impl FnMut(&Self),
+        on_progress: &dyn Fn(HandshakeProgress),
+    ) -> Result<HandshakeResult, ChannelError> {
+        let start = Instant::now();
+        let mut progress = HandshakeResult::new(self, from);
+
+        telemetry!(
+            channel_handshake,
+            &self.a_chain().id(),
+            &self.b_chain().id(),
+            self.a_side.port_id(),
+            "started"
+        );
+
+        let options = HandshakeOptions {
+            deadline: self.handshake_deadline(start)?,
+            ..options
+        };
+
+        self.check_clients_active()?;
+
+        let result = self
+            .retry_handshake("open channel", &options, |channel| {
+                let result = channel.do_chan_open_handshake(from, &mut progress, on_progress);
+                after_step(channel);
+                result
+            })
+            .map_err(|e| match e.detail() {
+                ChannelErrorDetail::MaxRetry(detail) => {
+                    let attempts = detail.tries;
+                    ChannelError::handshake_timed_out(
+                        attempts,
+                        self.handshake_progress(&progress),
+                        e,
+                    )
+                }
+                _ => e,
+            });
+
+        telemetry!({
+            let (a_chain, b_chain) = (self.a_chain().id(), self.b_chain().id());
+            let port = self.a_side.port_id();
+            let outcome = if result.is_ok() { "opened" } else { "failed" };
+
+            ibc_telemetry::global().channel_handshake(&a_chain, &b_chain, port, outcome);
+
+            // Every attempt at a step but the first is a retry
+            for (step, attempts) in &progress.attempts {
+                ibc_telemetry::global().handshake_retries(
+                    &a_chain,
+                    &b_chain,
+                    port,
+                    &format!("{step:?}"),
+                    u64::from(attempts.saturating_sub(1)),
+                );
+            }
+        });
+
+        result?;
+
+        progress.step = HandshakeStep::Open;
+        progress.a_channel_id = self.a_channel_id().cloned();
+        progress.b_channel_id = self.b_channel_id().cloned();
+        progress.elapsed = start.elapsed();
+
+        Ok(progress)
+    }
+
+    /// Returns how far the handshake got given its `progress`, along with the channel
+    /// ids known so far, eg. to report them when the handshake fails.
+    fn handshake_progress(&self, progress: &HandshakeResult) -> ChannelHandshakeProgress {
+        ChannelHandshakeProgress {
+            step: progress.step,
+            a_channel_id: self.a_channel_id().cloned(),
+            b_channel_id: self.b_channel_id().cloned(),
+            last_state: progress.states,
+        }
+    }
+
+    /// Repeatedly performs the given handshake step until it completes,
+    /// following the handshake retry strategy, and at the latest until the deadline.
+    ///
+    /// With `options.events`, a failed attempt is retried as soon as a handshake event
+    /// of this channel is received, and at the latest after the delay of the retry
+    /// strategy, so that the number of attempts and the longest wait between them are
+    /// the same with or without events.
+    fn retry_handshake(
+        &mut self,
+        operation: &str,
+        options: &HandshakeOptions,
+        mut step: impl FnMut(&mut Self) -> Result<(), ChannelError>,
+    ) -> Result<(), ChannelError> {
+        // Collect the delays upfront so that they can be logged along with the failures
+        let delays: Vec<Duration> = self.handshake_retry_strategy()?.collect();
+        let deadline = options.deadline;
+        let start = Instant::now();
+
+        // With events, the delay is waited for when handling the failure, not by the retries
+        let retry_delays = match &options.events {
+            Some(_) => vec![Duration::ZERO; delays.len()],
+            None => delays.clone(),
+        };
+
+        // Every step is attempted at least once, the deadline is checked before waiting
+        // for the next attempt
+        let result = retry_with_index(retry_delays, |index| {
+            match step(self) {
+                Ok(()) => RetryResult::Ok(()),
+                Err(e) if !e.should_retry() => RetryResult::Err(e),
+                Err(e) => {
+                    // `index` is the number of the attempt which just failed, starting at 1
+                    let delay = delays.get(index.saturating_sub(1) as usize);
+
+                    // Never wait past the deadline for the next attempt
+                    if past_deadline(deadline, delay.copied().unwrap_or_default()) {
+                        warn!(
+                            "failed to {} at attempt {}, giving up as the next attempt \
+                            would be past the deadline: {}",
+                            operation, index, e
+                        );
+                        return RetryResult::Err(e);
+                    }
+
+                    match (delay, &options.events) {
+                        (Some(delay), Some(events)) => {
+                            warn!(
+                                "failed to {} at attempt {}, retrying on the next channel event \
+                                or in {}: {}",
+                                operation,
+                                index,
+                                PrettyDuration(delay),
+                                e
+                            );
+
+                            events.wait(self, *delay);
+                        }
+                        (Some(delay), None) => {
+                            warn!(
+                                "failed to {} at attempt {}, retrying in {}: {}",
+                                operation,
+                                index,
+                                PrettyDuration(delay),
+                                e
+                            );
+                        }
+                        (None, _) => {}
+                    }
+
+                    RetryResult::Retry(e)
+                }
+            }
+        });
+
+        result.map_err(|err| {
+            let elapsed = start.elapsed();
+
+            error!(
+                "failed to {} after {} attempts in {}",
+                operation,
+                err.tries,
+                PrettyDuration(&elapsed)
+            );
+
+            handshake_retry::from_retry_error(
+                err,
+                format!(
+                    "failed to {} for channel {}/{} on chain {} and channel {}/{} on chain {}",
+                    operation,
+                    self.src_port_id(),
+                    PrettyOption(&self.src_channel_id()),
+                    self.src_chain().id(),
+                    self.dst_port_id(),
+                    PrettyOption(&self.dst_channel_id()),
+                    self.dst_chain().id(),
+                ),
+                elapsed,
+            )
+        })
+    }
+
+    /// Sends a channel close handshake message.
+    /// The message sent depends on the chain status of the channel ends.
+    fn do_chan_close_handshake(&mut self) -> Result<(), ChannelError> {
+        let ChannelEnds {
+            a_channel,
+            a_height,
+            b_channel,
+            b_height,
+        } = self.update_channel_and_query_ends()?;
+        let (a_state, b_state) = (*a_channel.state(), *b_channel.state());
+        debug!(
+            "do_chan_close_handshake with channel end states: {} at height {} on {}, {} at height {} on {}",
+            a_state,
+            a_height,
+            self.a_chain().id(),
+            b_state,
+            b_height,
+            self.b_chain().id(),
+        );
+
+        match (a_state, b_state) {
+            // send the CloseInit message to chain a (source)
+            (State::Open, State::Open) => {
+                self.flipped()
+                    .build_chan_close_init_and_send()
+                    .map_err(|e| {
+                        error!("failed ChanCloseInit {}: {}", self.a_side, e);
+                        e
+                    })?;
             }
 
-            // send the Confirm message to chain b (destination)
-            (State::Open, State::TryOpen) => {
-                self.build_chan_open_confirm_and_send().map_err(|e| {
-                    error!("failed ChanOpenConfirm {}: {}", self.b_side, e);
+            // send the CloseConfirm message to chain b (destination)
+            (State::Closed, State::Open) => {
+                self.build_chan_close_confirm_and_send().map_err(|e| {
+                    error!("failed ChanCloseConfirm {}: {}", self.b_side, e);
                     e
                 })?;
             }
 
-            // send the Confirm message to chain a (source)
-            (State::TryOpen, State::Open) => {
+            // send the CloseConfirm message to chain a (source)
+            (State::Open, State::Closed) => {
                 self.flipped()
-                    .build_chan_open_confirm_and_send()
+                    .build_chan_close_confirm_and_send()
                     .map_err(|e| {
-                        error!("failed ChanOpenConfirm {}: {}", self.a_side, e);
+                        error!("failed ChanCloseConfirm {}: {}", self.a_side, e);
                         e
                     })?;
             }
 
-            (State::Open, State::Open) => {
-                info!("channel handshake already finished for {}", self);
+            (State::Closed, State::Closed) => {
+                info!("channel close handshake already finished for {}", self);
                 return Ok(());
             }
 
             (a_state, b_state) => {
                 warn!(
-                    "do_conn_open_handshake does not handle channel end state combination: \
+                    "do_chan_close_handshake does not handle channel end state combination: \
                     {}-{}, {}-{}. will retry to account for RPC node data availability issues.",
                     self.a_chain().id(),
                     a_state,
@@ -698,38 +2017,39 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         Err(ChannelError::handshake_finalize())
     }
 
-    /// Executes the channel handshake protocol (ICS004)
-    fn handshake(&mut self) -> Result<(), ChannelError> {
-        let max_block_times = self.max_block_times()?;
-
-        retry_with_index(handshake_retry::default_strategy(max_block_times), |_| {
-            if let Err(e) = self.do_chan_open_handshake() {
-                if e.is_expired_or_frozen_error() {
-                    RetryResult::Err(e)
-                } else {
-                    RetryResult::Retry(e)
-                }
-            } else {
-                RetryResult::Ok(())
-            }
-        })
-        .map_err(|err| {
-            error!("failed to open channel after {} retries", err.tries);
+    /// Closes the channel on both chains.
+    ///
+    /// If both channel ends are open, a `MsgChannelCloseInit` is sent to the source
+    /// chain first, followed by a `MsgChannelCloseConfirm` to the destination chain.
+    /// If one of the ends is already closed, only the confirmation step is performed.
+    pub fn close(&mut self) -> Result<(), ChannelError> {
+        self.close_handshake()
+    }
 
-            handshake_retry::from_retry_error(
-                err,
-                format!("failed to finish channel handshake for {:?}", self),
-            )
-        })?;
+    /// Executes the channel close handshake protocol (ICS004) on an open channel
+    #[instrument(
+        name = "channel.close_handshake",
+        level = "error",
+        skip_all,
+        fields(
+            src_chain = %self.a_chain().id(),
+            dst_chain = %self.b_chain().id(),
+            src_port = %self.a_side.port_id(),
+            dst_port = %self.b_side.port_id(),
+        )
+    )]
+    fn close_handshake(&mut self) -> Result<(), ChannelError> {
+        let options = HandshakeOptions {
+            deadline: self.overall_deadline,
+            ..HandshakeOptions::default()
+        };
 
-        Ok(())
+        self.retry_handshake("close channel", &options, Self::do_chan_close_handshake)
     }
 
     pub fn counterparty_state(&self) -> Result<State, ChannelError> {
         // Source channel ID must be specified
-        let channel_id = self
-            .src_channel_id()
-            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let channel_id = self.require_src_channel_id()?;
 
         let channel_deps =
             channel_connection_client(self.src_chain(), self.src_port_id(), channel_id)
@@ -806,90 +2126,491 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     }
 
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, ChannelError> {
+        self.build_update_client_on_dst_trusting(height, None)
+    }
+
+    /// Builds the messages updating the client on the destination chain to `height`,
+    /// from `trusted_height` if given. No update is built when the client already
+    /// trusts a height at or above `height`.
+    fn build_update_client_on_dst_trusting(
+        &self,
+        height: Height,
+        trusted_height: Option<Height>,
+    ) -> Result<Vec<Any>, ChannelError> {
         let client = ForeignClient::restore(
             self.dst_client_id().clone(),
             self.dst_chain().clone(),
             self.src_chain().clone(),
         );
 
-        client.wait_and_build_update_client(height).map_err(|e| {
-            ChannelError::client_operation(self.dst_client_id().clone(), self.dst_chain().id(), e)
-        })
+        client
+            .wait_and_build_update_client_with_trusted(height, trusted_height)
+            .map_err(|e| {
+                ChannelError::client_operation(
+                    self.dst_client_id().clone(),
+                    self.dst_chain().id(),
+                    e,
+                )
+            })
     }
 
-    pub fn build_chan_open_init(&self) -> Result<Vec<Any>, ChannelError> {
-        let signer = self
-            .dst_chain()
-            .get_signer()
-            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+    /// Relays the packets sent over this channel in both directions, along with their
+    /// acknowledgements and timeouts, until `cancel` receives a message or is
+    /// disconnected.
+    ///
+    /// The events of both chains are scheduled on a [`Link`] for each direction, which
+    /// skips the packets already received, batches the messages within the limits of
+    /// the destination chain and times out the expired packets. The packets pending on
+    /// either chain, eg. those sent before the relaying started, are cleared right
+    /// away and then every `clear_interval`.
+    ///
+    /// Failing to relay is logged and does not stop the loop, unless a client is
+    /// expired or frozen.
+    pub fn relay_packets(
+        &self,
+        cancel: &crossbeam_channel::Receiver<()>,
+        clear_interval: Duration,
+    ) -> Result<(), LinkError> {
+        /// How often the scheduled messages are submitted, and the submitted
+        /// transactions confirmed, without new events.
+        const RELAY_TICK: Duration = Duration::from_secs(1);
+
+        let mut a_to_b = Link::new(self.clone(), true)?;
+        let mut b_to_a = Link::new(self.flipped(), true)?;
+
+        let a_events = self.a_chain().subscribe().map_err(LinkError::relayer)?;
+        let b_events = self.b_chain().subscribe().map_err(LinkError::relayer)?;
+
+        let mut next_clearing = Instant::now();
+
+        loop {
+            if Instant::now() >= next_clearing {
+                clear_packets(&a_to_b)?;
+                clear_packets(&b_to_a)?;
+                next_clearing = Instant::now() + clear_interval;
+            }
 
-        let counterparty = Counterparty::new(self.src_port_id().clone(), None);
+            select! {
+                recv(cancel) -> _ => return Ok(()),
+                recv(a_events) -> batch => {
+                    let batch = batch.map_err(|_| {
+                        LinkError::channel(ChannelError::subscription_closed(self.a_chain().id()))
+                    })?;
+                    schedule_events(&a_to_b, &batch)?;
+                },
+                recv(b_events) -> batch => {
+                    let batch = batch.map_err(|_| {
+                        LinkError::channel(ChannelError::subscription_closed(self.b_chain().id()))
+                    })?;
+                    schedule_events(&b_to_a, &batch)?;
+                },
+                default(RELAY_TICK) => {},
+            }
 
-        // If the user supplied a version, use that.
-        // Otherwise, either use the version defined for the `transfer`
-        // or an empty version if the port is non-standard.
-        let version = self
-            .dst_version()
-            .cloned()
-            .or_else(|| version::default_by_port(self.dst_port_id()))
-            .unwrap_or_else(|| {
-                warn!(
-                    chain = %self.dst_chain().id(),
-                    channel = ?self.dst_channel_id(),
-                    port = %self.dst_port_id(),
-                    "no version specified for the channel, falling back on empty version"
-                );
+            execute_schedule(&mut a_to_b)?;
+            execute_schedule(&mut b_to_a)?;
+        }
+    }
 
-                Version::empty()
-            });
+    /// Returns the packets sent by chain a over this channel which chain b did not
+    /// receive yet. Nothing is relayed, this is meant to audit the channel.
+    pub fn scan_pending_packets(&self) -> Result<Vec<PendingPacket>, ChannelError> {
+        let pending = self
+            .flipped()
+            .query_unreceived_packets()?
+            .into_iter()
+            .map(PendingPacket::from)
+            .collect();
 
-        let channel = ChannelEnd::new(
-            State::Init,
-            self.ordering,
-            counterparty,
-            vec![self.dst_connection_id().clone()],
-            version,
-        );
+        Ok(pending)
+    }
 
-        // Build the domain type message
-        let new_msg = MsgChannelOpenInit {
-            port_id: self.dst_port_id().clone(),
-            channel,
-            signer,
+    /// Returns the packets sent by the destination chain over this channel which
+    /// the source chain did not receive.
+    fn query_unreceived_packets(&self) -> Result<Vec<Packet>, ChannelError> {
+        let (src_channel_id, dst_channel_id) = match (self.src_channel_id(), self.dst_channel_id())
+        {
+            (Some(src), Some(dst)) => (src, dst),
+            (None, _) => return Err(ChannelError::missing_local_channel_id()),
+            (_, None) => return Err(ChannelError::missing_counterparty_channel_id()),
         };
 
-        Ok(vec![new_msg.to_any()])
-    }
+        let (commitments, height) = self
+            .dst_chain()
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: self.dst_port_id().clone(),
+                channel_id: dst_channel_id.clone(),
+                pagination: Some(PageRequest::all()),
+            })
+            .map_err(|e| ChannelError::chain_query(self.dst_chain().id(), e))?;
 
-    pub fn build_chan_open_init_and_send(&self) -> Result<IbcEvent, ChannelError> {
-        let dst_msgs = self.build_chan_open_init()?;
+        if commitments.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let sequences = self
+            .src_chain()
+            .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                port_id: self.src_port_id().clone(),
+                channel_id: src_channel_id.clone(),
+                packet_commitment_sequences: commitments,
+            })
+            .map_err(|e| ChannelError::chain_query(self.src_chain().id(), e))?;
 
-        let tm = TrackedMsgs::new_static(dst_msgs, "ChannelOpenInit");
+        if sequences.is_empty() {
+            return Ok(vec![]);
+        }
 
         let events = self
             .dst_chain()
-            .send_messages_and_wait_commit(tm)
-            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+            .query_packet_events(QueryPacketEventDataRequest {
+                event_id: WithBlockDataType::SendPacket,
+                source_channel_id: dst_channel_id.clone(),
+                source_port_id: self.dst_port_id().clone(),
+                destination_channel_id: src_channel_id.clone(),
+                destination_port_id: self.src_port_id().clone(),
+                sequences,
+                height: Qualified::SmallerEqual(QueryHeight::Specific(height)),
+            })
+            .map_err(|e| ChannelError::chain_query(self.dst_chain().id(), e))?;
 
-        // Find the relevant event for channel open init
-        let result = events
+        Ok(events
             .into_iter()
-            .find(|event_with_height| {
-                matches!(event_with_height.event, IbcEvent::OpenInitChannel(_))
-                    || matches!(event_with_height.event, IbcEvent::ChainError(_))
+            .filter_map(|event_with_height| match event_with_height.event {
+                IbcEvent::SendPacket(event) => Some(event.packet),
+                _ => None,
             })
-            .ok_or_else(|| {
-                ChannelError::missing_event("no chan init event was in the response".to_string())
-            })?;
+            .collect())
+    }
+
+    /// Returns the height of the source chain at which to build the proofs of the
+    /// source channel end `src_channel`, as queried at the latest height, along with
+    /// the height of the client on the destination when the proofs are built at a
+    /// height that client already trusts.
+    ///
+    /// This is the latest height, unless the destination chain has
+    /// `handshake_proofs_at_trusted_height` enabled. The proofs are then built at the
+    /// latest height its client already trusts, so that they can be verified without
+    /// updating the client first, provided the source channel end was already in the
+    /// same state at that height.
+    fn src_proofs_query_height(
+        &self,
+        src_channel_id: &ChannelId,
+        src_channel: &ChannelEnd,
+    ) -> Result<(Height, Option<Height>), ChannelError> {
+        let latest_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let dst_config = self.dst_chain().config().map_err(ChannelError::relayer)?;
+        if !dst_config.handshake_proofs_at_trusted_height {
+            return Ok((latest_height, None));
+        }
+
+        let (client_state, _) = self
+            .dst_chain()
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.dst_client_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let client_height = client_state.latest_height();
+        let query_height = trusted_proofs_query_height(latest_height, client_height);
+        if query_height == latest_height {
+            return Ok((latest_height, None));
+        }
+
+        let channel_at_height = self.src_chain().query_channel(
+            QueryChannelRequest {
+                port_id: self.src_port_id().clone(),
+                channel_id: src_channel_id.clone(),
+                height: QueryHeight::Specific(query_height),
+            },
+            IncludeProof::No,
+        );
+
+        match channel_at_height {
+            Ok((channel, _)) if &channel == src_channel => Ok((query_height, Some(client_height))),
+            _ => {
+                debug!(
+                    chain = %self.src_chain().id(),
+                    "channel {} was not yet in state {} at height {} trusted by client {} on {}, \
+                    building the proofs at the latest height {}",
+                    src_channel_id,
+                    src_channel.state(),
+                    query_height,
+                    self.dst_client_id(),
+                    self.dst_chain().id(),
+                    latest_height
+                );
+
+                Ok((latest_height, None))
+            }
+        }
+    }
+
+    pub fn build_chan_open_init(&self) -> Result<Vec<Any>, ChannelError> {
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let counterparty = Counterparty::new(self.src_port_id().clone(), None);
+
+        // If the user supplied a version, use that.
+        // Otherwise, either use the version defined for the `transfer`
+        // or an empty version if the port is non-standard.
+        let version = self
+            .dst_version()
+            .cloned()
+            .or_else(|| version::default_by_port(self.dst_port_id()))
+            .unwrap_or_else(|| {
+                warn!(
+                    chain = %self.dst_chain().id(),
+                    channel = ?self.dst_channel_id(),
+                    port = %self.dst_port_id(),
+                    "no version specified for the channel, falling back on empty version"
+                );
+
+                Version::empty()
+            });
+
+        let channel = ChannelEnd::new(
+            State::Init,
+            self.ordering,
+            counterparty,
+            vec![self.dst_connection_id().clone()],
+            version,
+        );
+
+        // Build the domain type message
+        let new_msg = MsgChannelOpenInit {
+            port_id: self.dst_port_id().clone(),
+            channel,
+            signer,
+        };
+
+        Ok(vec![new_msg.to_any()])
+    }
+
+    /// Looks for a channel on the destination chain, on the port and connection of
+    /// this channel, which is at least in state `Init` and already has the source
+    /// port as its counterparty port. Returns the id of the first such channel.
+    pub fn find_existing_channel(&self) -> Result<Option<ChannelId>, ChannelError> {
+        let channels = self.query_dst_connection_channels()?;
+
+        Ok(find_channel_with_counterparty_port(
+            &channels,
+            self.dst_port_id(),
+            self.src_port_id(),
+        ))
+    }
+
+    /// Looks on chain `a` for an open channel on the connection of this channel, with
+    /// its ports and ordering, whose counterparty end on chain `b` is open as well.
+    /// Returns the ids of the two ends of the channel with the lowest id on chain `a`,
+    /// and logs the other channels which qualify.
+    pub fn find_open_channel(&self) -> Result<Option<(ChannelId, ChannelId)>, ChannelError> {
+        let channels = self
+            .a_chain()
+            .query_connection_channels(QueryConnectionChannelsRequest {
+                connection_id: self.a_side.connection_id().clone(),
+                pagination: Some(PageRequest::all()),
+            })
+            .map_err(|e| ChannelError::query(self.a_chain().id(), e))?;
+
+        let mut found = Vec::new();
+
+        for (a_channel_id, b_channel_id) in find_open_channels(
+            &channels,
+            self.a_side.port_id(),
+            self.b_side.port_id(),
+            self.ordering,
+        ) {
+            let (b_channel, _) = self
+                .b_chain()
+                .query_channel(
+                    QueryChannelRequest {
+                        port_id: self.b_side.port_id().clone(),
+                        channel_id: b_channel_id.clone(),
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )
+                .map_err(|e| ChannelError::query(self.b_chain().id(), e))?;
+
+            if b_channel.is_open() && b_channel.counterparty().channel_id() == Some(&a_channel_id) {
+                found.push((a_channel_id, b_channel_id));
+            }
+        }
+
+        let mut found = found.into_iter();
+        let chosen = found.next();
+
+        let others: Vec<ChannelId> = found.map(|(a_channel_id, _)| a_channel_id).collect();
+        if let Some((a_channel_id, _)) = &chosen {
+            if !others.is_empty() {
+                info!(
+                    chain = %self.a_chain().id(),
+                    "picked channel {} among the open channels with the same ports: {}",
+                    a_channel_id,
+                    PrettySlice(&others)
+                );
+            }
+        }
+
+        Ok(chosen)
+    }
+
+    /// Looks for a channel in state `Init` on the destination chain, on the port and
+    /// connection of this channel, whose counterparty is the source port and either
+    /// the source channel or no channel yet. Such a channel results from both chains
+    /// sending `ChanOpenInit` concurrently (crossing hellos), and must be the one
+    /// which `ChanOpenTry` moves forward rather than opening yet another channel.
+    pub fn find_crossing_hello(
+        &self,
+        src_channel_id: &ChannelId,
+    ) -> Result<Option<ChannelId>, ChannelError> {
+        let channels = self.query_dst_connection_channels()?;
+
+        Ok(find_crossing_hello_channel(
+            &channels,
+            self.dst_port_id(),
+            self.src_port_id(),
+            src_channel_id,
+        ))
+    }
+
+    fn query_dst_connection_channels(&self) -> Result<Vec<IdentifiedChannelEnd>, ChannelError> {
+        self.dst_chain()
+            .query_connection_channels(QueryConnectionChannelsRequest {
+                connection_id: self.dst_connection_id().clone(),
+                pagination: Some(PageRequest::all()),
+            })
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))
+    }
+
+    /// Submits the message of the given handshake step to the destination chain with
+    /// `submit`, and records the outcome and the duration of the attempt in the
+    /// telemetry metrics.
+    fn record_handshake_step(
+        &self,
+        step: HandshakeStep,
+        submit: impl FnOnce(&Self) -> Result<IbcEventWithHeight, ChannelError>,
+    ) -> Result<IbcEventWithHeight, ChannelError> {
+        let start = Instant::now();
+        let result = submit(self);
+        let elapsed = start.elapsed();
+
+        debug!(
+            chain = %self.dst_chain().id(),
+            "{:?} step attempt took {}",
+            step,
+            PrettyDuration(&elapsed)
+        );
+
+        telemetry!({
+            // The channel created by the step, if any, or the one already on the destination
+            let channel_id = result
+                .as_ref()
+                .ok()
+                .and_then(|result| extract_channel_id(&result.event).ok())
+                .or_else(|| self.dst_channel_id());
+            let status = if result.is_ok() { "success" } else { "failure" };
+
+            ibc_telemetry::global().handshake_step(
+                &self.src_chain().id(),
+                &self.dst_chain().id(),
+                self.dst_port_id(),
+                channel_id,
+                &format!("{step:?}"),
+                status,
+                elapsed.as_millis() as u64,
+            );
 
-        match &result.event {
-            IbcEvent::OpenInitChannel(_) => {
-                info!("🎊  {} => {}", self.dst_chain().id(), result);
-                Ok(result.event)
+            if let Err(e) = &result {
+                ibc_telemetry::global().channel_handshake_error(
+                    &self.src_chain().id(),
+                    &self.dst_chain().id(),
+                    self.dst_port_id(),
+                    channel_id,
+                    &format!("{step:?}"),
+                    e.code(),
+                );
             }
-            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
-            _ => Err(ChannelError::invalid_event(result.event)),
+        });
+
+        result
+    }
+
+    pub fn build_chan_open_init_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        self.submit_chan_open_init().map(|result| result.event)
+    }
+
+    /// Builds and sends `ChanOpenInit` to the destination chain like
+    /// [`Channel::build_chan_open_init_and_send`], but returns all the `OpenInitChannel`
+    /// events of the response, in order, rather than the first one. This lets callers
+    /// which batch several channels in a transaction correlate each event to its port.
+    pub fn build_chan_open_init_and_send_all(&self) -> Result<Vec<IbcEvent>, ChannelError> {
+        let dst_msgs = self.build_chan_open_init()?;
+
+        let events: Vec<IbcEventWithHeight> =
+            send_msgs(self.dst_chain(), dst_msgs, "ChannelOpenInit")?
+                .into_iter()
+                .filter(|event_with_height| {
+                    matches!(event_with_height.event, IbcEvent::OpenInitChannel(_))
+                })
+                .collect();
+
+        if events.is_empty() {
+            return Err(ChannelError::missing_event(
+                "no ChannelOpenInit event was in the response".to_string(),
+            ));
+        }
+
+        for event in &events {
+            log_handshake_event("🎊", &self.dst_chain().id(), event);
         }
+
+        Ok(events.into_iter().map(|event| event.event).collect())
+    }
+
+    /// Builds and sends `ChanOpenInit` to the destination chain, returning its event
+    /// along with the height at which it was emitted.
+    fn submit_chan_open_init(&self) -> Result<IbcEventWithHeight, ChannelError> {
+        // A channel left over by a previous run without persisted state would be
+        // a candidate for this handshake, opening another one makes it a zombie.
+        match self.find_existing_channel() {
+            Ok(Some(channel_id)) => warn!(
+                chain = %self.dst_chain().id(),
+                port = %self.dst_port_id(),
+                connection = %self.dst_connection_id(),
+                "channel {} already exists with counterparty port {}, a new channel will be \
+                opened anyway",
+                channel_id,
+                self.src_port_id(),
+            ),
+            Ok(None) => {}
+            Err(e) => warn!(
+                chain = %self.dst_chain().id(),
+                "failed to look for existing channels before ChanOpenInit: {}",
+                e
+            ),
+        }
+
+        let dst_msgs = self.build_chan_open_init()?;
+
+        let result = send_and_extract(self.dst_chain(), dst_msgs, "ChannelOpenInit", |event| {
+            matches!(event, IbcEvent::OpenInitChannel(_))
+        })?;
+
+        log_handshake_event("🎊", &self.dst_chain().id(), &result);
+        Ok(result)
     }
 
     /// Retrieves the channel from destination and compares it
@@ -903,11 +2624,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     fn validated_expected_channel(
         &self,
         msg_type: ChannelMsgType,
+        expected_version: Option<&Version>,
     ) -> Result<ChannelEnd, ChannelError> {
         // Destination channel ID must be specified
-        let dst_channel_id = self
-            .dst_channel_id()
-            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+        let dst_channel_id = self.require_dst_channel_id()?;
 
         // If there is a channel present on the destination chain,
         // the counterparty should look like this:
@@ -918,6 +2638,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         let highest_state = match msg_type {
             ChannelMsgType::OpenAck => State::TryOpen,
             ChannelMsgType::OpenConfirm => State::TryOpen,
+            ChannelMsgType::CloseInit => State::Open,
             ChannelMsgType::CloseConfirm => State::Open,
             _ => State::Uninitialized,
         };
@@ -927,7 +2648,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             self.ordering,
             counterparty,
             vec![self.dst_connection_id().clone()],
-            Version::empty(),
+            expected_version.cloned().unwrap_or_else(Version::empty),
         );
 
         // Retrieve existing channel
@@ -956,9 +2677,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
 
     pub fn build_chan_open_try(&self) -> Result<Vec<Any>, ChannelError> {
         // Source channel ID must be specified
-        let src_channel_id = self
-            .src_channel_id()
-            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let src_channel_id = self.require_src_channel_id()?;
 
         // Channel must exist on source
         let (src_channel, _) = self
@@ -973,6 +2692,34 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        let (query_height, trusted_height) =
+            self.src_proofs_query_height(src_channel_id, &src_channel)?;
+
+        self.build_chan_open_try_trusting(&src_channel, query_height, trusted_height)
+    }
+
+    /// Builds `ChanOpenTry` for the source channel end `src_channel`, as observed at
+    /// `query_height` of the source chain, eg. from its `ChanOpenInit` event, rather
+    /// than querying the source channel end again. The proofs are built at that height.
+    pub fn build_chan_open_try_from_end(
+        &self,
+        src_channel: &ChannelEnd,
+        query_height: Height,
+    ) -> Result<Vec<Any>, ChannelError> {
+        self.build_chan_open_try_trusting(src_channel, query_height, None)
+    }
+
+    /// Builds `ChanOpenTry` like [`Channel::build_chan_open_try_from_end`], the client
+    /// on the destination being updated from `trusted_height` if given.
+    fn build_chan_open_try_trusting(
+        &self,
+        src_channel: &ChannelEnd,
+        query_height: Height,
+        trusted_height: Option<Height>,
+    ) -> Result<Vec<Any>, ChannelError> {
+        // Source channel ID must be specified
+        let src_channel_id = self.require_src_channel_id()?;
+
         if src_channel.counterparty().port_id() != self.dst_port_id() {
             return Err(ChannelError::mismatch_port(
                 self.dst_chain().id(),
@@ -994,24 +2741,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
-
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
-        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+        let mut msgs = self.build_update_client_on_dst_trusting(proofs.height(), trusted_height)?;
 
         let counterparty =
             Counterparty::new(self.src_port_id().clone(), self.src_channel_id().cloned());
 
-        // Re-use the version that was either set on ChanOpenInit or overwritten by the application.
-        let version = src_channel.version().clone();
+        // Use the version given for the destination end if any, otherwise re-use the version
+        // that was either set on ChanOpenInit or overwritten by the application.
+        let version = proposed_version(
+            self.src_version(),
+            self.dst_version(),
+            src_channel.version(),
+        )?;
 
         let channel = ChannelEnd::new(
             State::TryOpen,
@@ -1027,10 +2774,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .get_signer()
             .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
 
-        let previous_channel_id = if src_channel.counterparty().channel_id.is_none() {
-            self.b_side.channel_id.clone()
-        } else {
-            src_channel.counterparty().channel_id.clone()
+        let previous_channel_id = match (
+            src_channel.counterparty().channel_id(),
+            self.dst_channel_id(),
+        ) {
+            (Some(channel_id), _) | (None, Some(channel_id)) => Some(channel_id.clone()),
+            // With crossing hellos the destination end already exists in state `Init`
+            (None, None) => self.find_crossing_hello(src_channel_id)?,
         };
 
         // Build the domain type message
@@ -1048,47 +2798,31 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     }
 
     pub fn build_chan_open_try_and_send(&self) -> Result<IbcEvent, ChannelError> {
-        let dst_msgs = self.build_chan_open_try()?;
-
-        let tm = TrackedMsgs::new_static(dst_msgs, "ChannelOpenTry");
+        self.submit_chan_open_try().map(|result| result.event)
+    }
 
-        let events = self
-            .dst_chain()
-            .send_messages_and_wait_commit(tm)
-            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+    /// Builds and sends `ChanOpenTry` to the destination chain, returning its event
+    /// along with the height at which it was emitted.
+    fn submit_chan_open_try(&self) -> Result<IbcEventWithHeight, ChannelError> {
+        let dst_msgs = self.build_chan_open_try()?;
 
-        // Find the relevant event for channel open try
-        let result = events
-            .into_iter()
-            .find(|events_with_height| {
-                matches!(events_with_height.event, IbcEvent::OpenTryChannel(_))
-                    || matches!(events_with_height.event, IbcEvent::ChainError(_))
-            })
-            .ok_or_else(|| {
-                ChannelError::missing_event("no chan try event was in the response".to_string())
-            })?;
+        let result = send_and_extract(self.dst_chain(), dst_msgs, "ChannelOpenTry", |event| {
+            matches!(event, IbcEvent::OpenTryChannel(_))
+        })?;
 
-        match &result.event {
-            IbcEvent::OpenTryChannel(_) => {
-                info!("🎊  {} => {}", self.dst_chain().id(), result);
-                Ok(result.event)
-            }
-            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
-            _ => Err(ChannelError::invalid_event(result.event)),
-        }
+        log_handshake_event("🎊", &self.dst_chain().id(), &result);
+        Ok(result)
     }
 
     pub fn build_chan_open_ack(&self) -> Result<Vec<Any>, ChannelError> {
         // Source and destination channel IDs must be specified
-        let src_channel_id = self
-            .src_channel_id()
-            .ok_or_else(ChannelError::missing_local_channel_id)?;
-        let dst_channel_id = self
-            .dst_channel_id()
-            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+        let src_channel_id = self.require_src_channel_id()?;
+        let dst_channel_id = self.require_dst_channel_id()?;
 
         // Check that the destination chain will accept the Ack message
-        self.validated_expected_channel(ChannelMsgType::OpenAck)?;
+        // The version may still be narrowed down by the application during
+        // the Try step, so it cannot be checked at this point.
+        self.validated_expected_channel(ChannelMsgType::OpenAck, None)?;
 
         // Channel must exist on source
         let (src_channel, _) = self
@@ -1103,6 +2837,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        // The version chosen on ChanOpenTry must be the one requested for the destination
+        validate_channel_version(self.dst_version(), src_channel.version())?;
+
         // Connection must exist on destination
         self.dst_chain()
             .query_connection(
@@ -1114,10 +2851,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, trusted_height) =
+            self.src_proofs_query_height(src_channel_id, &src_channel)?;
 
         let proofs = self
             .src_chain()
@@ -1125,7 +2860,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
-        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+        let mut msgs = self.build_update_client_on_dst_trusting(proofs.height(), trusted_height)?;
 
         // Get signer
         let signer = self
@@ -1148,37 +2883,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     }
 
     pub fn build_chan_open_ack_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        self.submit_chan_open_ack().map(|result| result.event)
+    }
+
+    /// Builds and sends `ChanOpenAck` to the destination chain, returning its event
+    /// along with the height at which it was emitted.
+    fn submit_chan_open_ack(&self) -> Result<IbcEventWithHeight, ChannelError> {
         fn do_build_chan_open_ack_and_send<ChainA: ChainHandle, ChainB: ChainHandle>(
             channel: &Channel<ChainA, ChainB>,
-        ) -> Result<IbcEvent, ChannelError> {
+        ) -> Result<IbcEventWithHeight, ChannelError> {
             let dst_msgs = channel.build_chan_open_ack()?;
 
-            let tm = TrackedMsgs::new_static(dst_msgs, "ChannelOpenAck");
-
-            let events = channel
-                .dst_chain()
-                .send_messages_and_wait_commit(tm)
-                .map_err(|e| ChannelError::submit(channel.dst_chain().id(), e))?;
-
-            // Find the relevant event for channel open ack
-            let result = events
-                .into_iter()
-                .find(|event_with_height| {
-                    matches!(event_with_height.event, IbcEvent::OpenAckChannel(_))
-                        || matches!(event_with_height.event, IbcEvent::ChainError(_))
-                })
-                .ok_or_else(|| {
-                    ChannelError::missing_event("no chan ack event was in the response".to_string())
+            let result =
+                send_and_extract(channel.dst_chain(), dst_msgs, "ChannelOpenAck", |event| {
+                    matches!(event, IbcEvent::OpenAckChannel(_))
                 })?;
 
-            match &result.event {
-                IbcEvent::OpenAckChannel(_) => {
-                    info!("🎊  {} => {}", channel.dst_chain().id(), result);
-                    Ok(result.event)
-                }
-                IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
-                _ => Err(ChannelError::invalid_event(result.event)),
-            }
+            log_handshake_event("🎊", &channel.dst_chain().id(), &result);
+            Ok(result)
         }
 
         do_build_chan_open_ack_and_send(self).map_err(|e| {
@@ -1189,18 +2911,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
 
     pub fn build_chan_open_confirm(&self) -> Result<Vec<Any>, ChannelError> {
         // Source and destination channel IDs must be specified
-        let src_channel_id = self
-            .src_channel_id()
-            .ok_or_else(ChannelError::missing_local_channel_id)?;
-        let dst_channel_id = self
-            .dst_channel_id()
-            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
-
-        // Check that the destination chain will accept the message
-        self.validated_expected_channel(ChannelMsgType::OpenConfirm)?;
+        let src_channel_id = self.require_src_channel_id()?;
+        let dst_channel_id = self.require_dst_channel_id()?;
 
         // Channel must exist on source
-        self.src_chain()
+        let (src_channel, _) = self
+            .src_chain()
             .query_channel(
                 QueryChannelRequest {
                     port_id: self.src_port_id().clone(),
@@ -1211,6 +2927,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        // Check that the destination chain will accept the message.
+        // The version has been negotiated already, both ends must agree on it.
+        self.validated_expected_channel(ChannelMsgType::OpenConfirm, Some(src_channel.version()))?;
+
         // Connection must exist on destination
         self.dst_chain()
             .query_connection(
@@ -1222,10 +2942,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, trusted_height) =
+            self.src_proofs_query_height(src_channel_id, &src_channel)?;
 
         let proofs = self
             .src_chain()
@@ -1233,7 +2951,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
-        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+        let mut msgs = self.build_update_client_on_dst_trusting(proofs.height(), trusted_height)?;
 
         // Get signer
         let signer = self
@@ -1254,38 +2972,26 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     }
 
     pub fn build_chan_open_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        self.submit_chan_open_confirm().map(|result| result.event)
+    }
+
+    /// Builds and sends `ChanOpenConfirm` to the destination chain, returning its event
+    /// along with the height at which it was emitted.
+    fn submit_chan_open_confirm(&self) -> Result<IbcEventWithHeight, ChannelError> {
         fn do_build_chan_open_confirm_and_send<ChainA: ChainHandle, ChainB: ChainHandle>(
             channel: &Channel<ChainA, ChainB>,
-        ) -> Result<IbcEvent, ChannelError> {
+        ) -> Result<IbcEventWithHeight, ChannelError> {
             let dst_msgs = channel.build_chan_open_confirm()?;
 
-            let tm = TrackedMsgs::new_static(dst_msgs, "ChannelOpenConfirm");
-            let events = channel
-                .dst_chain()
-                .send_messages_and_wait_commit(tm)
-                .map_err(|e| ChannelError::submit(channel.dst_chain().id(), e))?;
-
-            // Find the relevant event for channel open confirm
-            let result = events
-                .into_iter()
-                .find(|event_with_height| {
-                    matches!(event_with_height.event, IbcEvent::OpenConfirmChannel(_))
-                        || matches!(event_with_height.event, IbcEvent::ChainError(_))
-                })
-                .ok_or_else(|| {
-                    ChannelError::missing_event(
-                        "no chan confirm event was in the response".to_string(),
-                    )
-                })?;
+            let result = send_and_extract(
+                channel.dst_chain(),
+                dst_msgs,
+                "ChannelOpenConfirm",
+                |event| matches!(event, IbcEvent::OpenConfirmChannel(_)),
+            )?;
 
-            match &result.event {
-                IbcEvent::OpenConfirmChannel(_) => {
-                    info!("🎊  {} => {}", channel.dst_chain().id(), result);
-                    Ok(result.event)
-                }
-                IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
-                _ => Err(ChannelError::invalid_event(result.event)),
-            }
+            log_handshake_event("🎊", &channel.dst_chain().id(), &result);
+            Ok(result)
         }
 
         do_build_chan_open_confirm_and_send(self).map_err(|e| {
@@ -1294,11 +3000,73 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         })
     }
 
+    /// Sends `ChanOpenInit` to the destination chain, records the new channel id and
+    /// returns the resulting destination channel end.
+    pub fn send_chan_open_init(&mut self) -> Result<ChannelOpenOutcome, ChannelError> {
+        let event = self.build_chan_open_init_and_send()?;
+        self.open_outcome(event)
+    }
+
+    /// Sends `ChanOpenTry` to the destination chain, records the new channel id and
+    /// returns the resulting destination channel end.
+    pub fn send_chan_open_try(&mut self) -> Result<ChannelOpenOutcome, ChannelError> {
+        let event = self.build_chan_open_try_and_send()?;
+        self.open_outcome(event)
+    }
+
+    /// Sends `ChanOpenAck` to the destination chain, records its channel id and
+    /// returns the resulting destination channel end.
+    pub fn send_chan_open_ack(&mut self) -> Result<ChannelOpenOutcome, ChannelError> {
+        let event = self.build_chan_open_ack_and_send()?;
+        self.open_outcome(event)
+    }
+
+    /// Sends `ChanOpenConfirm` to the destination chain, records its channel id and
+    /// returns the resulting destination channel end.
+    pub fn send_chan_open_confirm(&mut self) -> Result<ChannelOpenOutcome, ChannelError> {
+        let event = self.build_chan_open_confirm_and_send()?;
+        self.open_outcome(event)
+    }
+
+    /// Records the destination channel id from the event of a handshake message, then
+    /// queries the destination channel end as it results from that message.
+    ///
+    /// The message is committed at this point, so a failure of the query is logged
+    /// rather than returned, for the caller not to send the message again.
+    fn open_outcome(&mut self, event: IbcEvent) -> Result<ChannelOpenOutcome, ChannelError> {
+        let channel_id = extract_channel_id(&event)?.clone();
+        self.b_side.set_channel_id(&channel_id);
+
+        let channel_end = self
+            .dst_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::chain_query(self.dst_chain().id(), e))
+            .and_then(|height| Ok((self.b_channel(Some(&channel_id), height)?, height)));
+
+        let (channel_end, height) = match channel_end {
+            Ok((channel_end, height)) => (Some(channel_end), Some(height)),
+            Err(e) => {
+                warn!(
+                    chain = %self.dst_chain().id(),
+                    "failed to query channel {} after its handshake message was committed: {}",
+                    channel_id,
+                    e
+                );
+                (None, None)
+            }
+        };
+
+        Ok(ChannelOpenOutcome {
+            channel_id,
+            channel_end,
+            height,
+            event,
+        })
+    }
+
     pub fn build_chan_close_init(&self) -> Result<Vec<Any>, ChannelError> {
         // Destination channel ID must be specified
-        let dst_channel_id = self
-            .dst_channel_id()
-            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+        let dst_channel_id = self.require_dst_channel_id()?;
 
         // Channel must exist on destination
         self.dst_chain()
@@ -1330,48 +3098,22 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     pub fn build_chan_close_init_and_send(&self) -> Result<IbcEvent, ChannelError> {
         let dst_msgs = self.build_chan_close_init()?;
 
-        let tm = TrackedMsgs::new_static(dst_msgs, "ChannelCloseInit");
-
-        let events = self
-            .dst_chain()
-            .send_messages_and_wait_commit(tm)
-            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
-
-        // Find the relevant event for channel close init
-        let result = events
-            .into_iter()
-            .find(|event_with_height| {
-                matches!(event_with_height.event, IbcEvent::CloseInitChannel(_))
-                    || matches!(event_with_height.event, IbcEvent::ChainError(_))
-            })
-            .ok_or_else(|| {
-                ChannelError::missing_event("no chan init event was in the response".to_string())
-            })?;
+        let result = send_and_extract(self.dst_chain(), dst_msgs, "ChannelCloseInit", |event| {
+            matches!(event, IbcEvent::CloseInitChannel(_))
+        })?;
 
-        match &result.event {
-            IbcEvent::CloseInitChannel(_) => {
-                info!("👋 {} => {}", self.dst_chain().id(), result);
-                Ok(result.event)
-            }
-            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
-            _ => Err(ChannelError::invalid_event(result.event)),
-        }
+        log_handshake_event("👋", &self.dst_chain().id(), &result);
+        Ok(result.event)
     }
 
     pub fn build_chan_close_confirm(&self) -> Result<Vec<Any>, ChannelError> {
         // Source and destination channel IDs must be specified
-        let src_channel_id = self
-            .src_channel_id()
-            .ok_or_else(ChannelError::missing_local_channel_id)?;
-        let dst_channel_id = self
-            .dst_channel_id()
-            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
-
-        // Check that the destination chain will accept the message
-        self.validated_expected_channel(ChannelMsgType::CloseConfirm)?;
+        let src_channel_id = self.require_src_channel_id()?;
+        let dst_channel_id = self.require_dst_channel_id()?;
 
         // Channel must exist on source
-        self.src_chain()
+        let (src_channel, _) = self
+            .src_chain()
             .query_channel(
                 QueryChannelRequest {
                     port_id: self.src_port_id().clone(),
@@ -1382,6 +3124,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        // Check that the destination chain will accept the message.
+        // The version has been negotiated already, both ends must agree on it.
+        self.validated_expected_channel(ChannelMsgType::CloseConfirm, Some(src_channel.version()))?;
+
         // Connection must exist on destination
         self.dst_chain()
             .query_connection(
@@ -1393,10 +3139,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, trusted_height) =
+            self.src_proofs_query_height(src_channel_id, &src_channel)?;
 
         let proofs = self
             .src_chain()
@@ -1404,7 +3148,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
-        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+        let mut msgs = self.build_update_client_on_dst_trusting(proofs.height(), trusted_height)?;
 
         // Get signer
         let signer = self
@@ -1427,32 +3171,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     pub fn build_chan_close_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
         let dst_msgs = self.build_chan_close_confirm()?;
 
-        let tm = TrackedMsgs::new_static(dst_msgs, "ChannelCloseConfirm");
+        let result =
+            send_and_extract(self.dst_chain(), dst_msgs, "ChannelCloseConfirm", |event| {
+                matches!(event, IbcEvent::CloseConfirmChannel(_))
+            })?;
 
-        let events = self
-            .dst_chain()
-            .send_messages_and_wait_commit(tm)
-            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
-
-        // Find the relevant event for channel close confirm
-        let result = events
-            .into_iter()
-            .find(|event_with_height| {
-                matches!(event_with_height.event, IbcEvent::CloseConfirmChannel(_))
-                    || matches!(event_with_height.event, IbcEvent::ChainError(_))
-            })
-            .ok_or_else(|| {
-                ChannelError::missing_event("no chan confirm event was in the response".to_string())
-            })?;
-
-        match &result.event {
-            IbcEvent::CloseConfirmChannel(_) => {
-                info!("👋 {} => {}", self.dst_chain().id(), result);
-                Ok(result.event)
-            }
-            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
-            _ => Err(ChannelError::invalid_event(result.event)),
-        }
+        log_handshake_event("👋", &self.dst_chain().id(), &result);
+        Ok(result.event)
     }
 
     pub fn map_chain<ChainC: ChainHandle, ChainD: ChainHandle>(
@@ -1465,19 +3190,356 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             a_side: self.a_side.map_chain(mapper_a),
             b_side: self.b_side.map_chain(mapper_b),
             connection_delay: self.connection_delay,
+            overall_deadline: self.overall_deadline,
         }
     }
 }
 
+/// Logs a handshake event committed on `chain_id`, with the channel and the height
+/// as fields rather than as a dump of the whole event.
+fn log_handshake_event(emoji: &str, chain_id: &ChainId, result: &IbcEventWithHeight) {
+    info!(
+        chain = %chain_id,
+        channel_id = %PrettyOption(&extract_channel_id(&result.event).ok()),
+        height = %result.height,
+        "{} {} committed",
+        emoji,
+        result.event.event_type().as_str()
+    );
+}
+
 pub fn extract_channel_id(event: &IbcEvent) -> Result<&ChannelId, ChannelError> {
     match event {
         IbcEvent::OpenInitChannel(ev) => ev.channel_id(),
         IbcEvent::OpenTryChannel(ev) => ev.channel_id(),
         IbcEvent::OpenAckChannel(ev) => ev.channel_id(),
         IbcEvent::OpenConfirmChannel(ev) => ev.channel_id(),
+        IbcEvent::CloseInitChannel(ev) => Some(ev.channel_id()),
+        IbcEvent::CloseConfirmChannel(ev) => ev.channel_id(),
         _ => None,
     }
-    .ok_or_else(|| ChannelError::missing_event("cannot extract channel_id from result".to_string()))
+    .ok_or_else(|| ChannelError::missing_channel_id(event.clone()))
+}
+
+/// The steps of the channel opening handshake, in the order in which they happen.
+/// Each step is named after the message which it sends, except for `Open` which
+/// denotes a completed handshake.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum HandshakeStep {
+    Init,
+    Try,
+    Ack,
+    Confirm,
+    Open,
+}
+
+impl HandshakeStep {
+    /// Returns the next step of the handshake given the states of the two channel
+    /// ends, or `None` if the combination of states cannot occur during a handshake.
+    pub fn from_states(a_state: State, b_state: State) -> Option<Self> {
+        match (a_state, b_state) {
+            (State::Uninitialized, State::Uninitialized) => Some(Self::Init),
+
+            (State::Init, State::Uninitialized)
+            | (State::Uninitialized, State::Init)
+            | (State::Init, State::Init) => Some(Self::Try),
+
+            (State::Init, State::TryOpen)
+            | (State::TryOpen, State::Init)
+            | (State::TryOpen, State::TryOpen) => Some(Self::Ack),
+
+            (State::Open, State::TryOpen) | (State::TryOpen, State::Open) => Some(Self::Confirm),
+
+            (State::Open, State::Open) => Some(Self::Open),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the side of the channel whose chain this step sends its message to,
+    /// given the states of the two channel ends. The step is sent to chain `a`
+    /// unless only the end on chain `b` is behind.
+    pub fn destination(self, a_state: State, b_state: State) -> ChannelEndSide {
+        match (self, a_state, b_state) {
+            (Self::Try, State::Init, State::Uninitialized)
+            | (Self::Ack, State::TryOpen, State::Init)
+            | (Self::Confirm, State::Open, _) => ChannelEndSide::B,
+            _ => ChannelEndSide::A,
+        }
+    }
+}
+
+/// Parses a step from its short name, eg. `try`, or from the name of the message
+/// which it sends, eg. `ChanOpenTry`, ignoring case.
+impl FromStr for HandshakeStep {
+    type Err = ChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "init" | "chanopeninit" => Ok(Self::Init),
+            "try" | "chanopentry" => Ok(Self::Try),
+            "ack" | "chanopenack" => Ok(Self::Ack),
+            "confirm" | "chanopenconfirm" => Ok(Self::Confirm),
+            "open" => Ok(Self::Open),
+            _ => Err(ChannelError::unknown_handshake_step(s.to_string())),
+        }
+    }
+}
+
+/// Names a step after the message which it sends.
+impl Display for HandshakeStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Init => write!(f, "ChanOpenInit"),
+            Self::Try => write!(f, "ChanOpenTry"),
+            Self::Ack => write!(f, "ChanOpenAck"),
+            Self::Confirm => write!(f, "ChanOpenConfirm"),
+            Self::Open => write!(f, "Open"),
+        }
+    }
+}
+
+/// One of the two ends of a [`Channel`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChannelEndSide {
+    A,
+    B,
+}
+
+/// How the channel opening or closing handshake is retried.
+#[derive(Default)]
+struct HandshakeOptions {
+    /// The time after which the handshake gives up, if any.
+    deadline: Option<Instant>,
+    /// The events driving the handshake, if any, instead of the retry delays.
+    events: Option<HandshakeEvents>,
+}
+
+/// The event subscriptions of both chains of a channel.
+struct HandshakeEvents {
+    a: Subscription,
+    b: Subscription,
+}
+
+impl HandshakeEvents {
+    /// Waits until either chain emits a handshake event of the given channel,
+    /// and at most for `timeout`. Returns whether such an event arrived.
+    fn wait<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        channel: &Channel<ChainA, ChainB>,
+        timeout: Duration,
+    ) -> bool {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return false;
+            }
+
+            let (batch, on_a) = select! {
+                recv(self.a) -> batch => (batch, true),
+                recv(self.b) -> batch => (batch, false),
+                default(remaining) => return false,
+            };
+
+            match batch {
+                Ok(batch) => {
+                    let relevant = match batch.as_ref() {
+                        Ok(batch) => batch
+                            .events
+                            .iter()
+                            .any(|event| channel.is_handshake_event(&event.event, on_a)),
+                        Err(e) => {
+                            debug!(
+                                "ignoring event monitor error during channel handshake: {}",
+                                e
+                            );
+                            false
+                        }
+                    };
+
+                    if relevant {
+                        return true;
+                    }
+                }
+                Err(RecvError) => {
+                    // The subscription is gone, fall back to polling after the timeout
+                    std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// The two ends of a channel, along with the heights at which they were queried.
+struct ChannelEnds {
+    a_channel: ChannelEnd,
+    a_height: Height,
+    b_channel: ChannelEnd,
+    b_height: Height,
+}
+
+/// The destination channel end resulting from a step of the channel opening
+/// handshake, eg. with the version negotiated by the destination on `ChanOpenTry`.
+#[derive(Clone, Debug)]
+pub struct ChannelOpenOutcome {
+    pub channel_id: ChannelId,
+    /// The destination channel end, `None` if it could not be queried after the
+    /// handshake message was committed.
+    pub channel_end: Option<ChannelEnd>,
+    /// The height at which the channel end was queried.
+    pub height: Option<Height>,
+    /// The event emitted for the handshake message.
+    pub event: IbcEvent,
+}
+
+/// A step of the channel opening handshake which completed: the message of the step
+/// was committed on the chain with the given id at the given height or, for
+/// [`HandshakeStep::Open`], both channel ends were found to be open, with the
+/// height at which the end on that chain was queried.
+#[derive(Clone, Debug, Serialize)]
+pub struct HandshakeProgress {
+    pub step: HandshakeStep,
+    pub chain_id: ChainId,
+    pub channel_id: Option<ChannelId>,
+    pub height: Height,
+}
+
+impl HandshakeProgress {
+    fn new(step: HandshakeStep, chain_id: ChainId, result: &IbcEventWithHeight) -> Self {
+        Self {
+            step,
+            chain_id,
+            channel_id: extract_channel_id(&result.event).ok().cloned(),
+            height: result.height,
+        }
+    }
+}
+
+/// The outcome of a single action of the channel opening handshake, performed by
+/// [`Channel::step`].
+#[derive(Debug)]
+pub enum ChannelStep {
+    /// The message of the given step was submitted and committed, the handshake
+    /// can proceed with the next step.
+    Pending {
+        step: HandshakeStep,
+        event: Box<IbcEvent>,
+    },
+    /// Both channel ends are open.
+    Done,
+    /// Nothing could be submitted, eg. because a chain lags behind or the submission
+    /// failed, the step should be performed again later.
+    NeedsRetry(Box<ChannelError>),
+}
+
+/// The outcome of the channel opening handshake.
+#[derive(Clone, Debug, Serialize)]
+pub struct HandshakeResult {
+    /// The step which the handshake has reached, [`HandshakeStep::Open`] once completed.
+    pub step: HandshakeStep,
+    pub a_channel_id: Option<ChannelId>,
+    pub b_channel_id: Option<ChannelId>,
+    /// The steps whose message was submitted during the handshake, in order.
+    pub steps: Vec<HandshakeStep>,
+    /// The events of the messages submitted during the handshake, in order.
+    pub events: Vec<IbcEvent>,
+    /// The number of attempts made at each step of the handshake.
+    pub attempts: BTreeMap<HandshakeStep, u32>,
+    /// The time it took to perform the handshake.
+    pub elapsed: Duration,
+    /// Whether both channel ends were found open without submitting any message.
+    pub already_open: bool,
+    /// The states of the channel ends on chain a and b when they were last queried.
+    pub states: Option<(State, State)>,
+}
+
+impl HandshakeResult {
+    fn new<ChainA: ChainHandle, ChainB: ChainHandle>(
+        channel: &Channel<ChainA, ChainB>,
+        step: HandshakeStep,
+    ) -> Self {
+        Self {
+            step,
+            a_channel_id: channel.a_channel_id().cloned(),
+            b_channel_id: channel.b_channel_id().cloned(),
+            steps: vec![],
+            events: vec![],
+            attempts: BTreeMap::new(),
+            elapsed: Duration::ZERO,
+            already_open: false,
+            states: None,
+        }
+    }
+
+    /// Records the event of the message submitted for the current step.
+    fn record(&mut self, event: IbcEvent) {
+        self.steps.push(self.step);
+        self.events.push(event);
+    }
+}
+
+/// How far a channel opening handshake got before failing, see
+/// [`ChannelError::handshake_progress`]. The handshake can be resumed from `step`
+/// with [`Channel::resume_handshake`] rather than started over.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ChannelHandshakeProgress {
+    /// The step which the handshake was stuck at.
+    pub step: HandshakeStep,
+    pub a_channel_id: Option<ChannelId>,
+    pub b_channel_id: Option<ChannelId>,
+    /// The states of the channel ends on chain a and b when they were last queried.
+    pub last_state: Option<(State, State)>,
+}
+
+impl Display for ChannelHandshakeProgress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "with channel ids ({}, {})",
+            PrettyOption(&self.a_channel_id),
+            PrettyOption(&self.b_channel_id)
+        )?;
+
+        match self.last_state {
+            Some((a_state, b_state)) => write!(f, " in states ({}, {})", a_state, b_state),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A channel along with the outcome of its opening handshake.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound(serialize = "(): Serialize"))]
+pub struct ChannelHandshakeResult<ChainA: ChainHandle, ChainB: ChainHandle> {
+    pub channel: Channel<ChainA, ChainB>,
+    /// Whether both channel ends were already open, so that nothing was submitted.
+    pub already_open: bool,
+    /// The steps whose message was submitted during the handshake, in order.
+    pub phases_executed: Vec<HandshakeStep>,
+}
+
+impl<ChainA: ChainHandle, ChainB: ChainHandle> ChannelHandshakeResult<ChainA, ChainB> {
+    fn new(channel: Channel<ChainA, ChainB>, result: HandshakeResult) -> Self {
+        Self {
+            channel,
+            already_open: result.already_open,
+            phases_executed: result.steps,
+        }
+    }
+}
+
+/// The messages which a step of the channel opening handshake submits.
+#[derive(Clone, Debug)]
+pub struct HandshakeMessages {
+    /// The handshake step which the messages perform.
+    pub step: HandshakeStep,
+    /// The chain to which the messages are submitted.
+    pub chain_id: ChainId,
+    /// The messages, preceded by the client updates they require.
+    pub messages: Vec<Any>,
 }
 
 /// Enumeration of proof carrying ICS4 message, helper for relayer.
@@ -1486,6 +3548,7 @@ pub enum ChannelMsgType {
     OpenTry,
     OpenAck,
     OpenConfirm,
+    CloseInit,
     CloseConfirm,
 }
 
@@ -1494,22 +3557,2299 @@ fn check_destination_channel_state(
     existing_channel: &ChannelEnd,
     expected_channel: &ChannelEnd,
 ) -> Result<(), ChannelError> {
-    let good_connection_hops =
-        existing_channel.connection_hops() == expected_channel.connection_hops();
-
     // TODO: Refactor into a method
     let good_state = *existing_channel.state() as u32 <= *expected_channel.state() as u32;
+    if !good_state {
+        return Err(ChannelError::incompatible_state(
+            channel_id.clone(),
+            *expected_channel.state(),
+            *existing_channel.state(),
+        ));
+    }
+
+    let good_connection_hops =
+        existing_channel.connection_hops() == expected_channel.connection_hops();
     let good_channel_port_ids = existing_channel.counterparty().channel_id().is_none()
         || existing_channel.counterparty().channel_id()
             == expected_channel.counterparty().channel_id()
             && existing_channel.counterparty().port_id()
                 == expected_channel.counterparty().port_id();
 
-    // TODO: Check versions
+    if !(good_connection_hops && good_channel_port_ids) {
+        return Err(ChannelError::channel_already_exist(channel_id.clone()));
+    }
 
-    if good_state && good_connection_hops && good_channel_port_ids {
+    if versions_compatible(existing_channel.version(), expected_channel.version()) {
         Ok(())
     } else {
-        Err(ChannelError::channel_already_exist(channel_id.clone()))
+        Err(ChannelError::version_mismatch(
+            channel_id.clone(),
+            expected_channel.version().clone(),
+            existing_channel.version().clone(),
+        ))
+    }
+}
+
+/// Checks that an existing channel end is open and matches the expected one: same
+/// ordering, connection hops and counterparty and, unless the expected version is
+/// empty, the same version.
+fn verify_channel_end(
+    chain_id: ChainId,
+    port_channel_id: PortChannelId,
+    existing_channel: &ChannelEnd,
+    expected_channel: &ChannelEnd,
+) -> Result<(), ChannelError> {
+    let channel_id = &port_channel_id.channel_id;
+
+    if !existing_channel.is_open() {
+        return Err(ChannelError::channel_not_open(
+            chain_id,
+            channel_id.clone(),
+            *existing_channel.state(),
+        ));
+    }
+
+    if existing_channel.ordering() != expected_channel.ordering() {
+        return Err(ChannelError::mismatch_ordering(
+            chain_id,
+            channel_id.clone(),
+            *expected_channel.ordering(),
+            *existing_channel.ordering(),
+        ));
+    }
+
+    if existing_channel.connection_hops() != expected_channel.connection_hops() {
+        return Err(ChannelError::mismatch_connection_hops(
+            chain_id,
+            channel_id.clone(),
+            expected_channel.connection_hops().clone(),
+            existing_channel.connection_hops().clone(),
+        ));
+    }
+
+    let counterparty = existing_channel.counterparty();
+    let expected_counterparty = expected_channel.counterparty();
+
+    let counterparty_channel_id = match counterparty.channel_id() {
+        Some(counterparty_channel_id) => counterparty_channel_id,
+        None => {
+            return Err(ChannelError::incomplete_channel_state(
+                chain_id,
+                port_channel_id,
+            ))
+        }
+    };
+
+    if Some(counterparty_channel_id) != expected_counterparty.channel_id()
+        || counterparty.port_id() != expected_counterparty.port_id()
+    {
+        let expected_channel_id = expected_counterparty
+            .channel_id()
+            .cloned()
+            .unwrap_or_default();
+
+        return Err(ChannelError::mismatch_channel_ends(
+            chain_id,
+            port_channel_id,
+            PortChannelId::new(expected_channel_id, expected_counterparty.port_id().clone()),
+            PortChannelId::new(
+                counterparty_channel_id.clone(),
+                counterparty.port_id().clone(),
+            ),
+        ));
+    }
+
+    // Only the version is left to check
+    check_destination_channel_state(channel_id, existing_channel, expected_channel)
+}
+
+/// Submits to `chain` in a single batch the messages built for several channels,
+/// each made of the client updates it needs followed by its handshake message.
+/// The updates of a client to a given height are only submitted once, even when
+/// several channels need them.
+///
+/// Returns for each channel, in order, the event of its handshake message, found
+/// with `is_event`, or the reason why it is missing.
+fn send_batched_msgs<Chain: ChainHandle>(
+    chain: &Chain,
+    built: Vec<Result<Vec<Any>, ChannelError>>,
+    msg_type: &'static str,
+    is_event: impl Fn(usize, &IbcEvent) -> bool,
+) -> Vec<Result<IbcEvent, ChannelError>> {
+    let mut msgs = vec![];
+    let mut updates: Vec<(ClientId, Height)> = vec![];
+
+    let results: Vec<Result<(), ChannelError>> = built
+        .into_iter()
+        .map(|channel_msgs| {
+            let mut channel_msgs = channel_msgs?;
+            let msg = channel_msgs
+                .pop()
+                .ok_or_else(|| ChannelError::missing_event(format!("no {} message", msg_type)))?;
+
+            for update in channel_msgs {
+                match client_update_target(&update) {
+                    Some(target) if updates.contains(&target) => {}
+                    Some(target) => {
+                        updates.push(target);
+                        msgs.push(update);
+                    }
+                    None => msgs.push(update),
+                }
+            }
+            msgs.push(msg);
+
+            Ok(())
+        })
+        .collect();
+
+    // No channel could build its message
+    if msgs.is_empty() {
+        return results
+            .into_iter()
+            .map(|result| Err(result.expect_err("a message was built")))
+            .collect();
+    }
+
+    let events = match chain.send_messages_and_wait_commit(TrackedMsgs::new_static(msgs, msg_type))
+    {
+        Ok(events) => events,
+        Err(e) => {
+            error!("failed to submit the batched {} messages: {}", msg_type, e);
+
+            // Keep the read-only refusal visible to the callers, which must not retry it
+            let read_only = e.is_read_only_mode_error();
+            return results
+                .into_iter()
+                .map(|result| {
+                    result.and_then(|()| {
+                        Err(if read_only {
+                            ChannelError::submit(
+                                chain.id(),
+                                RelayerError::read_only_mode(chain.id()),
+                            )
+                        } else {
+                            ChannelError::missing_event(format!(
+                                "the batch of {} messages was not submitted to {}",
+                                msg_type,
+                                chain.id()
+                            ))
+                        })
+                    })
+                })
+                .collect();
+        }
+    };
+
+    let chain_error = events
+        .iter()
+        .find_map(|event_with_height| match &event_with_height.event {
+            IbcEvent::ChainError(e) => Some(e.clone()),
+            _ => None,
+        });
+
+    let mut events: Vec<Option<IbcEvent>> = events
+        .into_iter()
+        .map(|event_with_height| Some(event_with_height.event))
+        .collect();
+
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            result?;
+
+            // Claim the first event of this channel which was not claimed by another one
+            let event = events
+                .iter_mut()
+                .find(|event| matches!(event, Some(event) if is_event(i, event)))
+                .and_then(Option::take);
+
+            match (event, &chain_error) {
+                (Some(event), _) => Ok(event),
+                (None, Some(e)) => Err(ChannelError::tx_response(chain.id(), e.clone())),
+                (None, None) => Err(ChannelError::missing_event(format!(
+                    "no {} event was in the response",
+                    msg_type
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Returns the client updated by a `MsgUpdateClient` and the height of its header.
+fn client_update_target(msg: &Any) -> Option<(ClientId, Height)> {
+    if msg.type_url != update_client::TYPE_URL {
+        return None;
+    }
+
+    let msg = MsgUpdateClient::decode_vec(&msg.value).ok()?;
+    let header = AnyHeader::try_from(msg.header).ok()?;
+    Some((msg.client_id, header.height()))
+}
+
+/// Submits the messages of a channel handshake step to the chain and returns the event
+/// of the step, or the error of the chain if it comes first in the response.
+fn send_and_extract<Chain: ChainHandle>(
+    chain: &Chain,
+    msgs: Vec<Any>,
+    msg_type: &'static str,
+    is_expected: impl Fn(&IbcEvent) -> bool,
+) -> Result<IbcEventWithHeight, ChannelError> {
+    let tm = TrackedMsgs::new_static(msgs, msg_type);
+
+    let mut events = chain
+        .send_messages_and_wait_commit(tm)
+        .map_err(|e| ChannelError::submit(chain.id(), e))?;
+
+    // Taking the first event may attribute the wrong channel to the step
+    let expected = events
+        .iter()
+        .filter(|event_with_height| is_expected(&event_with_height.event))
+        .count();
+    if expected > 1 {
+        warn!(
+            chain = %chain.id(),
+            "found {} {} events in the response, using the first one",
+            expected,
+            msg_type
+        );
+    }
+
+    let position = events.iter().position(|event_with_height| {
+        is_expected(&event_with_height.event)
+            || matches!(event_with_height.event, IbcEvent::ChainError(_))
+    });
+
+    let result = match position {
+        Some(position) => events.swap_remove(position),
+        // The message of the step comes last, and so does its event
+        None => match events.pop() {
+            Some(event_with_height) => {
+                return Err(ChannelError::unexpected_event(
+                    chain.id(),
+                    msg_type.to_string(),
+                    event_with_height.event,
+                ))
+            }
+            None => {
+                return Err(ChannelError::missing_event(format!(
+                    "no {msg_type} event was in the response"
+                )))
+            }
+        },
+    };
+
+    match &result.event {
+        IbcEvent::ChainError(e) => Err(ChannelError::tx_response(chain.id(), e.clone())),
+        _ => Ok(result),
+    }
+}
+
+/// Submits the given messages to `chain` and waits for them to be committed, failing
+/// with [`ChannelError::tx_response`] if the chain reports an error.
+fn send_msgs<Chain: ChainHandle>(
+    chain: &Chain,
+    msgs: Vec<Any>,
+    msg_type: &'static str,
+) -> Result<Vec<IbcEventWithHeight>, ChannelError> {
+    let tm = TrackedMsgs::new_static(msgs, msg_type);
+    let events = chain
+        .send_messages_and_wait_commit(tm)
+        .map_err(|e| ChannelError::submit(chain.id(), e))?;
+
+    if let Some(IbcEvent::ChainError(e)) = events
+        .iter()
+        .map(|event_with_height| &event_with_height.event)
+        .find(|event| matches!(event, IbcEvent::ChainError(_)))
+    {
+        return Err(ChannelError::tx_response(chain.id(), e.clone()));
+    }
+
+    Ok(events)
+}
+
+/// The parameters of one of the channels opened by [`Channel::new_batch`].
+#[derive(Clone, Debug)]
+pub struct ChannelSpec {
+    pub ordering: Order,
+    pub a_port: PortId,
+    pub b_port: PortId,
+    pub version: Option<Version>,
+}
+
+/// A packet which was sent over a channel and not received yet by the counterparty
+/// chain, as returned by [`Channel::scan_pending_packets`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PendingPacket {
+    pub sequence: Sequence,
+    pub timeout_height: TimeoutHeight,
+    pub timeout_timestamp: Timestamp,
+    pub data: Vec<u8>,
+}
+
+impl From<Packet> for PendingPacket {
+    fn from(packet: Packet) -> Self {
+        Self {
+            sequence: packet.sequence,
+            timeout_height: packet.timeout_height,
+            timeout_timestamp: packet.timeout_timestamp,
+            data: packet.data,
+        }
+    }
+}
+
+/// Joins a query running on a scoped thread, resuming the panic of the thread if any.
+fn join_query<T>(handle: std::thread::ScopedJoinHandle<'_, T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+/// Fails with [`ChannelError::client_expired_or_frozen`] if the result of validating
+/// the state of `client` shows that it is expired or frozen, and only warns about
+/// any other failure.
+fn check_client_active<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+    result: Result<(AnyClientState, Option<Duration>), ForeignClientError>,
+) -> Result<(), ChannelError> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) if e.is_expired_or_frozen_error() => Err(ChannelError::client_expired_or_frozen(
+            client.id().clone(),
+            client.dst_chain().id(),
+            e,
+        )),
+        Err(e) => {
+            warn!(
+                chain = %client.dst_chain().id(),
+                "failed to check that client {} is active: {}",
+                client.id(),
+                e
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Whether waiting for the given delay from now would reach the deadline, if any.
+fn past_deadline(deadline: Option<Instant>, delay: Duration) -> bool {
+    match (deadline, Instant::now().checked_add(delay)) {
+        (Some(deadline), Some(next_attempt)) => next_attempt >= deadline,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Returns the id of the first channel on the given port which is in state `Init`
+/// or later, but not closed, and whose counterparty is on the given port.
+fn find_channel_with_counterparty_port(
+    channels: &[IdentifiedChannelEnd],
+    port_id: &PortId,
+    counterparty_port_id: &PortId,
+) -> Option<ChannelId> {
+    channels
+        .iter()
+        .find(|channel| {
+            let state = *channel.channel_end.state();
+
+            &channel.port_id == port_id
+                && matches!(state, State::Init | State::TryOpen | State::Open)
+                && channel.channel_end.counterparty().port_id() == counterparty_port_id
+        })
+        .map(|channel| channel.channel_id.clone())
+}
+
+/// Returns the height at which to query proofs on a chain whose latest height is
+/// `latest_height`, for them to be verified by a client of that chain whose latest
+/// height is `client_height`.
+///
+/// Proofs queried at some height are verified against the consensus state at the
+/// next height, so this is the height just below the height of the client, unless
+/// the chain is not there yet.
+fn trusted_proofs_query_height(latest_height: Height, client_height: Height) -> Height {
+    match client_height.decrement() {
+        Ok(trusted_height) => latest_height.min(trusted_height),
+        Err(_) => latest_height,
+    }
+}
+
+/// Returns the port and channel ids of the channels on the given connection of `chain`.
+fn connection_channel_ids<Chain: ChainHandle>(
+    chain: &Chain,
+    connection_id: &ConnectionId,
+) -> Result<Vec<(PortId, ChannelId)>, ChannelError> {
+    let channels = chain
+        .query_connection_channels(QueryConnectionChannelsRequest {
+            connection_id: connection_id.clone(),
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(|e| ChannelError::query(chain.id(), e))?;
+
+    Ok(channels
+        .into_iter()
+        .map(|channel| (channel.port_id, channel.channel_id))
+        .collect())
+}
+
+/// Returns the open channels on the given port with the given ordering, whose
+/// counterparty is a known channel on the given counterparty port, along with that
+/// counterparty channel. The channels are sorted by increasing channel number.
+fn find_open_channels(
+    channels: &[IdentifiedChannelEnd],
+    port_id: &PortId,
+    counterparty_port_id: &PortId,
+    ordering: Order,
+) -> Vec<(ChannelId, ChannelId)> {
+    let mut found: Vec<(ChannelId, ChannelId)> = channels
+        .iter()
+        .filter(|channel| {
+            &channel.port_id == port_id
+                && channel.channel_end.is_open()
+                && channel.channel_end.ordering() == &ordering
+                && channel.channel_end.counterparty().port_id() == counterparty_port_id
+        })
+        .filter_map(|channel| {
+            let counterparty_channel_id = channel.channel_end.counterparty().channel_id()?;
+            Some((channel.channel_id.clone(), counterparty_channel_id.clone()))
+        })
+        .collect();
+
+    // Channel ids compare as strings, which would put `channel-10` before `channel-2`
+    found.sort_by_key(|(channel_id, _)| {
+        let number = channel_id
+            .as_str()
+            .strip_prefix("channel-")
+            .and_then(|number| number.parse::<u64>().ok());
+        (number.unwrap_or(u64::MAX), channel_id.clone())
+    });
+
+    found
+}
+
+/// Returns the id of the first channel on the given port which is in state `Init`
+/// and whose counterparty is the given channel, or a channel not known yet on the
+/// given counterparty port.
+fn find_crossing_hello_channel(
+    channels: &[IdentifiedChannelEnd],
+    port_id: &PortId,
+    counterparty_port_id: &PortId,
+    counterparty_channel_id: &ChannelId,
+) -> Option<ChannelId> {
+    channels
+        .iter()
+        .find(|channel| {
+            let counterparty = channel.channel_end.counterparty();
+
+            &channel.port_id == port_id
+                && channel.channel_end.state_matches(&State::Init)
+                && counterparty.port_id() == counterparty_port_id
+                && (counterparty.channel_id().is_none()
+                    || counterparty.channel_id() == Some(counterparty_channel_id))
+        })
+        .map(|channel| channel.channel_id.clone())
+}
+
+/// Checks whether the version of an existing channel end is compatible with the
+/// expected one. An empty version on either side is compatible with any version,
+/// since it is not known yet. Otherwise the versions must be equal, except that
+/// JSON-encoded versions are compared on their `version` field, if any, or as
+/// JSON values, so that whitespace, key order and extra metadata do not matter.
+fn versions_compatible(existing: &Version, expected: &Version) -> bool {
+    if existing == &Version::empty() || expected == &Version::empty() || existing == expected {
+        return true;
+    }
+
+    match (json_version(existing), json_version(expected)) {
+        (Some(existing), Some(expected)) => existing == expected,
+        _ => false,
+    }
+}
+
+/// Returns the `version` field of a JSON-encoded version, or the whole JSON value
+/// when there is no such field, and `None` for a version which is not a JSON object.
+fn json_version(version: &Version) -> Option<serde_json::Value> {
+    let mut value = match serde_json::from_str::<serde_json::Value>(&version.0) {
+        Ok(value) if value.is_object() => value,
+        _ => return None,
+    };
+
+    match value.get_mut("version") {
+        Some(field) => Some(field.take()),
+        None => Some(value),
+    }
+}
+
+/// Checks that the version of the counterparty channel end, as negotiated on
+/// `ChanOpenTry`, is the version explicitly requested for this end, if any.
+fn validate_channel_version(
+    requested_version: Option<&Version>,
+    counterparty_version: &Version,
+) -> Result<(), ChannelError> {
+    match requested_version {
+        Some(requested) if requested != &Version::empty() && requested != counterparty_version => {
+            Err(ChannelError::incompatible_versions(
+                counterparty_version.clone(),
+                requested.clone(),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the version to propose for a channel end opened with `ChanOpenTry`,
+/// given the versions explicitly requested for each end, if any, and the version
+/// of the counterparty end.
+///
+/// An explicit version for the destination end takes precedence, so that
+/// applications expecting a version different from the one of the counterparty
+/// (eg. an encoded version for interchain accounts or fee middleware) can be
+/// accommodated. Versions requested for both ends must agree.
+fn proposed_version(
+    src_version: Option<&Version>,
+    dst_version: Option<&Version>,
+    counterparty_version: &Version,
+) -> Result<Version, ChannelError> {
+    match (src_version, dst_version) {
+        (Some(src), Some(dst)) if src != dst => Err(ChannelError::incompatible_versions(
+            src.clone(),
+            dst.clone(),
+        )),
+        (_, Some(dst)) => Ok(dst.clone()),
+        (_, None) => Ok(counterparty_version.clone()),
+    }
+}
+
+/// Schedules the messages relaying the packets pending on the source chain of `link`.
+fn clear_packets<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &Link<ChainA, ChainB>,
+) -> Result<(), LinkError> {
+    link.a_to_b
+        .schedule_packet_clearing(None)
+        .or_else(ignore_link_error)
+}
+
+/// Schedules the messages relaying the events of a batch from the source chain of `link`.
+fn schedule_events<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &Link<ChainA, ChainB>,
+    batch: &MonitorResult<EventBatch>,
+) -> Result<(), LinkError> {
+    match batch {
+        Ok(batch) => link
+            .a_to_b
+            .update_schedule(batch.clone())
+            .or_else(ignore_link_error),
+        Err(e) => {
+            error!(
+                "event source of chain '{}' failed: {}",
+                link.a_to_b.src_chain().id(),
+                e
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Submits the scheduled messages of `link` and confirms the submitted transactions.
+fn execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &mut Link<ChainA, ChainB>,
+) -> Result<(), LinkError> {
+    link.a_to_b.refresh_schedule().or_else(ignore_link_error)?;
+    link.a_to_b.execute_schedule().or_else(ignore_link_error)?;
+
+    let summary = link.a_to_b.process_pending_txs(Resubmit::No);
+
+    if !summary.is_empty() {
+        debug!("relayed packets: {:?}", summary);
+
+        let _path = &link.a_to_b;
+        telemetry!(crate::worker::packet::packet_metrics(
+            &crate::object::Packet {
+                dst_chain_id: _path.dst_chain().id(),
+                src_chain_id: _path.src_chain().id(),
+                src_channel_id: _path.src_channel_id().clone(),
+                src_port_id: _path.src_port_id().clone(),
+            },
+            &summary
+        ));
+    }
+
+    Ok(())
+}
+
+/// Logs a relaying error to retry on the next events, unless a client is expired or frozen.
+fn ignore_link_error(e: LinkError) -> Result<(), LinkError> {
+    if e.is_expired_or_frozen_error() {
+        Err(e)
+    } else {
+        error!("will retry: failed to relay packets: {}", e);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::test_utils::{
+        channel_end, connection_with_state, ends_in_state, mock_handle, recording_handle,
+        respond_as_client_host, respond_as_client_source, test_chain_config, test_channel,
+        test_handle, test_packet,
+    };
+
+    use alloc::sync::Arc;
+    use crossbeam_channel as channel;
+
+    use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_ics07_header;
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::core::ics02_client::events::NewBlock;
+    use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
+    use ibc_relayer_types::core::ics04_channel::events::{OpenInit, OpenTry, SendPacket};
+
+    use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use ibc_relayer_types::events::ChainError;
+    use ibc_relayer_types::proofs::Proofs;
+    use ibc_relayer_types::signer::Signer;
+    use ibc_relayer_types::timestamp::Timestamp;
+
+    use crate::chain::endpoint::ChainStatus;
+    use crate::chain::handle::ChainRequest;
+    use crate::chain::tracking::TrackingId;
+
+    use crate::chain::handle::BaseChainHandle;
+
+    fn assert_missing_local(result: Result<Vec<Any>, ChannelError>) {
+        let e = result.expect_err("expected missing local channel id error");
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::MissingLocalChannelId(_)),
+            "unexpected error: {e}"
+        );
+    }
+
+    fn assert_missing_counterparty(result: Result<Vec<Any>, ChannelError>) {
+        let e = result.expect_err("expected missing counterparty channel id error");
+        assert!(
+            matches!(
+                e.detail(),
+                ChannelErrorDetail::MissingCounterpartyChannelId(_)
+            ),
+            "unexpected error: {e}"
+        );
+    }
+
+    #[test]
+    fn require_channel_ids() {
+        let channel = test_channel(None, Some(ChannelId::new(1)));
+
+        assert!(channel.require_src_channel_id().is_err());
+        assert_eq!(
+            channel.require_dst_channel_id().ok(),
+            Some(&ChannelId::new(1))
+        );
+
+        let flipped = channel.flipped();
+
+        assert_eq!(
+            flipped.require_src_channel_id().ok(),
+            Some(&ChannelId::new(1))
+        );
+        assert!(flipped.require_dst_channel_id().is_err());
+    }
+
+    #[test]
+    fn display_is_compact() {
+        let channel = test_channel(Some(ChannelId::new(0)), None);
+
+        assert_eq!(
+            channel.to_string(),
+            "chain-a/07-tendermint-0/connection-0/transfer/channel-0 <-> \
+            chain-b/07-tendermint-0/connection-0/transfer/None [ORDER_UNORDERED]"
+        );
+    }
+
+    #[test]
+    fn close_confirm_rejects_closed_destination() {
+        let channel_id = ChannelId::new(0);
+        let counterparty_id = Some(ChannelId::new(1));
+
+        // This is the expected channel end built for `ChannelMsgType::CloseConfirm`
+        let expected = channel_end(State::Open, counterparty_id.clone());
+
+        let open = channel_end(State::Open, counterparty_id.clone());
+        assert!(check_destination_channel_state(&channel_id, &open, &expected).is_ok());
+
+        let closed = channel_end(State::Closed, counterparty_id);
+        let e = check_destination_channel_state(&channel_id, &closed, &expected)
+            .expect_err("expected incompatible state");
+        assert!(matches!(
+            e.detail(),
+            ChannelErrorDetail::IncompatibleState(_)
+        ));
+    }
+
+    #[test]
+    fn verify_channel_end_reports_the_failed_invariant() {
+        let port_channel_id = PortChannelId::new(ChannelId::new(0), PortId::transfer());
+        let expected = channel_end(State::Open, Some(ChannelId::new(1)));
+
+        let verify = |existing: &ChannelEnd| {
+            verify_channel_end(
+                ChainId::default(),
+                port_channel_id.clone(),
+                existing,
+                &expected,
+            )
+        };
+
+        assert!(verify(&expected).is_ok());
+
+        let closed = channel_end(State::Closed, Some(ChannelId::new(1)));
+        assert!(matches!(
+            verify(&closed).unwrap_err().detail(),
+            ChannelErrorDetail::ChannelNotOpen(_)
+        ));
+
+        let mut ordered = expected.clone();
+        ordered.ordering = Order::Ordered;
+        assert!(matches!(
+            verify(&ordered).unwrap_err().detail(),
+            ChannelErrorDetail::MismatchOrdering(_)
+        ));
+
+        let mut other_connection = expected.clone();
+        other_connection.connection_hops = vec![ConnectionId::new(1)];
+        assert!(matches!(
+            verify(&other_connection).unwrap_err().detail(),
+            ChannelErrorDetail::MismatchConnectionHops(_)
+        ));
+
+        let no_counterparty = channel_end(State::Open, None);
+        assert!(matches!(
+            verify(&no_counterparty).unwrap_err().detail(),
+            ChannelErrorDetail::IncompleteChannelState(_)
+        ));
+
+        let other_counterparty = channel_end(State::Open, Some(ChannelId::new(2)));
+        assert!(matches!(
+            verify(&other_counterparty).unwrap_err().detail(),
+            ChannelErrorDetail::MismatchChannelEnds(_)
+        ));
+
+        // An empty version is compatible with any other, so compare two known versions
+        let mut existing_version = expected.clone();
+        existing_version.version = Version::new("ics20-2".to_string());
+        let mut expected_version = expected.clone();
+        expected_version.version = Version::ics20();
+        let e = verify_channel_end(
+            ChainId::default(),
+            port_channel_id,
+            &existing_version,
+            &expected_version,
+        )
+        .unwrap_err();
+        assert!(matches!(e.detail(), ChannelErrorDetail::VersionMismatch(_)));
+    }
+
+    #[test]
+    fn verify_requires_channel_ids() {
+        let e = test_channel(Some(ChannelId::new(0)), None)
+            .verify()
+            .unwrap_err();
+
+        assert!(matches!(
+            e.detail(),
+            ChannelErrorDetail::MissingCounterpartyChannelId(_)
+        ));
+    }
+
+    #[test]
+    fn open_outcome_survives_a_failed_query() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), None);
+
+        let event = IbcEvent::from(OpenInit {
+            port_id: PortId::transfer(),
+            channel_id: Some(ChannelId::new(1)),
+            connection_id: ConnectionId::default(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: None,
+        });
+
+        // The test chains cannot be queried, the committed channel is reported nonetheless
+        let outcome = channel.open_outcome(event).unwrap();
+        assert_eq!(outcome.channel_id, ChannelId::new(1));
+        assert!(outcome.channel_end.is_none());
+        assert!(outcome.height.is_none());
+        assert_eq!(channel.b_channel_id(), Some(&ChannelId::new(1)));
+    }
+
+    #[test]
+    fn handshake_progress_reports_the_committed_event() {
+        let event = IbcEvent::from(OpenInit {
+            port_id: PortId::transfer(),
+            channel_id: Some(ChannelId::new(1)),
+            connection_id: ConnectionId::default(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: None,
+        });
+        let height = Height::new(0, 42).unwrap();
+
+        let progress = HandshakeProgress::new(
+            HandshakeStep::Init,
+            ChainId::from_string("chain-a"),
+            &IbcEventWithHeight::new(event, height),
+        );
+
+        assert_eq!(progress.step, HandshakeStep::Init);
+        assert_eq!(progress.chain_id, ChainId::from_string("chain-a"));
+        assert_eq!(progress.channel_id, Some(ChannelId::new(1)));
+        assert_eq!(progress.height, height);
+    }
+
+    #[test]
+    fn failed_handshakes_report_how_far_they_got() {
+        let channel = test_channel(Some(ChannelId::new(0)), None);
+
+        let mut result = HandshakeResult::new(&channel, HandshakeStep::Try);
+        result.states = Some((State::Init, State::Uninitialized));
+
+        let e = ChannelError::handshake_timed_out(
+            3,
+            channel.handshake_progress(&result),
+            ChannelError::missing_event("event".to_string()),
+        );
+
+        assert_eq!(
+            e.handshake_progress(),
+            Some(&ChannelHandshakeProgress {
+                step: HandshakeStep::Try,
+                a_channel_id: Some(ChannelId::new(0)),
+                b_channel_id: None,
+                last_state: Some((State::Init, State::Uninitialized)),
+            })
+        );
+        assert!(
+            e.to_string()
+                .contains("with channel ids (channel-0, None) in states (INIT, UNINITIALIZED)"),
+            "unexpected error: {e}"
+        );
+
+        let e = ChannelError::missing_event("event".to_string());
+        assert_eq!(e.handshake_progress(), None);
+    }
+
+    #[test]
+    fn past_deadline_accounts_for_the_next_delay() {
+        let deadline = Instant::now() + Duration::from_secs(60);
+
+        assert!(!past_deadline(None, Duration::MAX));
+        assert!(!past_deadline(Some(deadline), Duration::ZERO));
+        assert!(!past_deadline(Some(deadline), Duration::from_secs(1)));
+        assert!(past_deadline(Some(deadline), Duration::from_secs(120)));
+        assert!(past_deadline(Some(deadline), Duration::MAX));
+        assert!(past_deadline(Some(Instant::now()), Duration::ZERO));
+    }
+
+    fn open_try_event(port_id: &str, channel_id: u64, counterparty_port_id: &str) -> IbcEvent {
+        IbcEvent::from(OpenTry {
+            port_id: PortId::from_str(port_id).unwrap(),
+            channel_id: Some(ChannelId::new(channel_id)),
+            connection_id: ConnectionId::default(),
+            counterparty_port_id: PortId::from_str(counterparty_port_id).unwrap(),
+            counterparty_channel_id: Some(ChannelId::new(9)),
+        })
+    }
+
+    #[test]
+    fn handshake_events_of_the_channel() {
+        let channel = test_channel(Some(ChannelId::new(0)), None);
+
+        // The end on chain a is known
+        assert!(channel.is_handshake_event(&open_try_event("transfer", 0, "transfer"), true));
+        assert!(!channel.is_handshake_event(&open_try_event("transfer", 1, "transfer"), true));
+        assert!(!channel.is_handshake_event(&open_try_event("transfer", 0, "other"), true));
+        assert!(!channel.is_handshake_event(&open_try_event("other", 0, "transfer"), true));
+
+        // Any channel on the port of the end on chain b may be the one
+        assert!(channel.is_handshake_event(&open_try_event("transfer", 5, "transfer"), false));
+        assert!(!channel.is_handshake_event(&open_try_event("other", 5, "transfer"), false));
+
+        assert!(!channel.is_handshake_event(&IbcEvent::ChainError(ChainError::default()), true));
+    }
+
+    #[test]
+    fn handshake_events_wake_up_the_handshake() {
+        let channel = test_channel(Some(ChannelId::new(0)), None);
+
+        let (a_sender, a) = channel::unbounded();
+        let (_b_sender, b) = channel::unbounded();
+        let events = HandshakeEvents { a, b };
+        let timeout = Duration::from_millis(50);
+
+        let batch = |event: IbcEvent| {
+            let height = Height::new(0, 1).unwrap();
+            Arc::new(Ok(EventBatch {
+                chain_id: ChainId::from_string("chain-a"),
+                tracking_id: TrackingId::new_uuid(),
+                height,
+                events: vec![IbcEventWithHeight::new(event, height)],
+            }))
+        };
+
+        // Nothing happens until the timeout
+        assert!(!events.wait(&channel, timeout));
+
+        // Events of other channels are skipped
+        a_sender
+            .send(batch(open_try_event("other", 0, "transfer")))
+            .unwrap();
+        assert!(!events.wait(&channel, timeout));
+
+        a_sender
+            .send(batch(open_try_event("transfer", 0, "transfer")))
+            .unwrap();
+        assert!(events.wait(&channel, timeout));
+    }
+
+    #[test]
+    fn concurrent_queries_of_the_channel_ends_surface_chain_errors() {
+        let mut channel = test_channel(None, None);
+
+        let e = match channel.update_channel_and_query_ends() {
+            Ok(_) => panic!("expected the queries to fail"),
+            Err(e) => e,
+        };
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::ChainQuery(_)),
+            "unexpected error: {e}"
+        );
+    }
+
+    #[test]
+    fn channel_ends_which_do_not_point_back_are_an_error() {
+        // Each end has the given counterparty, whatever the queried channel
+        let ends_with_counterparty = |counterparty: u64| {
+            move |request| match request {
+                ChainRequest::QueryChannel { reply_to, .. } => {
+                    let end = channel_end(State::TryOpen, Some(ChannelId::new(counterparty)));
+                    reply_to.send(Ok((end, None))).unwrap();
+                }
+                request => ends_in_state(State::TryOpen)(request),
+            }
+        };
+
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        channel.a_side.chain = mock_handle("chain-a", ends_with_counterparty(5));
+        channel.b_side.chain = mock_handle("chain-b", ends_with_counterparty(7));
+
+        let e = match channel.update_channel_and_query_ends() {
+            Ok(_) => panic!("expected the mismatched channel ends to be an error"),
+            Err(e) => e,
+        };
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::MismatchChannelEnds(_)),
+            "unexpected error: {e}"
+        );
+    }
+
+    #[test]
+    fn channel_side_setters() {
+        let mut side = test_channel(None, None).a_side;
+
+        side.set_port_id(&PortId::from_str("oracle").unwrap());
+        side.set_channel_id(&ChannelId::new(7));
+
+        assert_eq!(side.port_id().as_str(), "oracle");
+        assert_eq!(side.channel_id(), Some(&ChannelId::new(7)));
+    }
+
+    #[test]
+    fn validate_channel_configuration() {
+        let assert_invalid = |channel: &Channel<BaseChainHandle, BaseChainHandle>| {
+            let e = channel
+                .validate()
+                .expect_err("expected invalid configuration");
+            assert!(
+                matches!(e.detail(), ChannelErrorDetail::InvalidConfig(_)),
+                "unexpected error: {e}"
+            );
+        };
+
+        let channel = test_channel(None, None);
+        assert!(channel.validate().is_ok());
+
+        let mut same_chain = channel.clone();
+        same_chain.b_side.chain = test_handle("chain-a");
+        assert_invalid(&same_chain);
+
+        let mut no_ordering = channel;
+        no_ordering.ordering = Order::None;
+        assert_invalid(&no_ordering);
+    }
+
+    #[test]
+    fn extract_channel_id_requires_a_channel_event() {
+        let event = IbcEvent::ChainError(ChainError::default());
+
+        let e = extract_channel_id(&event).expect_err("expected missing channel id");
+        assert!(matches!(
+            e.detail(),
+            ChannelErrorDetail::MissingChannelId(_)
+        ));
+    }
+
+    #[test]
+    fn destination_version_must_match_negotiated_version() {
+        let channel_id = ChannelId::new(0);
+        let counterparty_id = Some(ChannelId::new(1));
+
+        let mut expected = channel_end(State::TryOpen, counterparty_id.clone());
+        let mut existing = channel_end(State::TryOpen, counterparty_id);
+
+        // Nothing to check as long as the version is not known
+        existing.version = Version::ics20_with_fee();
+        assert!(check_destination_channel_state(&channel_id, &existing, &expected).is_ok());
+
+        expected.version = Version::ics20();
+        let e = check_destination_channel_state(&channel_id, &existing, &expected)
+            .expect_err("expected version mismatch");
+        assert!(matches!(e.detail(), ChannelErrorDetail::VersionMismatch(_)));
+
+        existing.version = Version::ics20();
+        assert!(check_destination_channel_state(&channel_id, &existing, &expected).is_ok());
+    }
+
+    #[test]
+    fn find_channel_with_counterparty_port_skips_unrelated_channels() {
+        let transfer = PortId::transfer();
+        let other = PortId::from_str("other").unwrap();
+
+        let identified = |port_id: &PortId, channel_id: u64, end: ChannelEnd| {
+            IdentifiedChannelEnd::new(port_id.clone(), ChannelId::new(channel_id), end)
+        };
+
+        let mut other_counterparty = channel_end(State::Open, None);
+        other_counterparty.remote = Counterparty::new(other.clone(), None);
+
+        let channels = vec![
+            identified(&other, 0, channel_end(State::Open, None)),
+            identified(&transfer, 1, channel_end(State::Closed, None)),
+            identified(&transfer, 2, other_counterparty),
+            identified(&transfer, 3, channel_end(State::Init, None)),
+            identified(&transfer, 4, channel_end(State::Open, None)),
+        ];
+
+        assert_eq!(
+            find_channel_with_counterparty_port(&channels, &transfer, &transfer),
+            Some(ChannelId::new(3))
+        );
+        assert_eq!(
+            find_channel_with_counterparty_port(&channels[..3], &transfer, &transfer),
+            None
+        );
+    }
+
+    #[test]
+    fn crossing_hellos_converge_on_the_initialized_channels() {
+        let transfer = PortId::transfer();
+
+        // Both chains sent `ChanOpenInit` concurrently: channel-3 on chain a and
+        // channel-7 on chain b, neither of them knowing about the other one yet.
+        // Chain b also has an unrelated channel, and one opened with channel-2 of a.
+        let chain_a = vec![IdentifiedChannelEnd::new(
+            transfer.clone(),
+            ChannelId::new(3),
+            channel_end(State::Init, None),
+        )];
+        let chain_b = vec![
+            IdentifiedChannelEnd::new(
+                transfer.clone(),
+                ChannelId::new(5),
+                channel_end(State::Open, Some(ChannelId::new(1))),
+            ),
+            IdentifiedChannelEnd::new(
+                transfer.clone(),
+                ChannelId::new(6),
+                channel_end(State::Init, Some(ChannelId::new(2))),
+            ),
+            IdentifiedChannelEnd::new(
+                transfer.clone(),
+                ChannelId::new(7),
+                channel_end(State::Init, None),
+            ),
+        ];
+
+        // `ChanOpenTry` on chain b for channel-3 of chain a reuses channel-7,
+        assert_eq!(
+            find_crossing_hello_channel(&chain_b, &transfer, &transfer, &ChannelId::new(3)),
+            Some(ChannelId::new(7))
+        );
+
+        // and `ChanOpenTry` on chain a for channel-7 of chain b reuses channel-3,
+        // so that the handshake completes with a single channel on each side
+        assert_eq!(
+            find_crossing_hello_channel(&chain_a, &transfer, &transfer, &ChannelId::new(7)),
+            Some(ChannelId::new(3))
+        );
+
+        // A channel already paired with another counterparty is never reused
+        assert_eq!(
+            find_crossing_hello_channel(&chain_b[..2], &transfer, &transfer, &ChannelId::new(3)),
+            None
+        );
+        assert_eq!(
+            find_crossing_hello_channel(&chain_b, &transfer, &transfer, &ChannelId::new(2)),
+            Some(ChannelId::new(6))
+        );
+    }
+
+    #[test]
+    fn proofs_are_queried_below_the_height_of_the_client() {
+        let height = |h| Height::new(0, h).unwrap();
+
+        // The proofs at height 9 are verified against the consensus state at height 10
+        assert_eq!(
+            trusted_proofs_query_height(height(15), height(10)),
+            height(9)
+        );
+        assert_eq!(
+            trusted_proofs_query_height(height(15), height(16)),
+            height(15)
+        );
+        assert_eq!(
+            trusted_proofs_query_height(height(15), height(1)),
+            height(15)
+        );
+    }
+
+    #[test]
+    fn only_expired_or_frozen_clients_stop_the_handshake() {
+        let client = ForeignClient::restore(
+            ClientId::default(),
+            test_handle("chain-b"),
+            test_handle("chain-a"),
+        );
+
+        let frozen = ForeignClientError::expired_or_frozen(
+            ClientId::default(),
+            ChainId::from_string("chain-b"),
+            "client state reports that client is frozen".to_string(),
+        );
+        let e = check_client_active(&client, Err(frozen))
+            .expect_err("expected the frozen client to stop the handshake");
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::ClientExpiredOrFrozen(_)),
+            "unexpected error: {e}"
+        );
+        assert!(!e.should_retry());
+
+        // A client which could not be checked is left to the handshake steps
+        let unknown = client
+            .validated_client_state()
+            .expect_err("expected the client state query to fail");
+        assert!(check_client_active(&client, Err(unknown)).is_ok());
+    }
+
+    #[test]
+    fn channels_are_discovered_on_the_connection() {
+        let chain = mock_handle("chain-a", |request| {
+            if let ChainRequest::QueryConnectionChannels { request, reply_to } = request {
+                assert_eq!(request.connection_id, ConnectionId::new(2));
+
+                let channels = vec![
+                    IdentifiedChannelEnd::new(
+                        PortId::transfer(),
+                        ChannelId::new(0),
+                        channel_end(State::Open, Some(ChannelId::new(4))),
+                    ),
+                    IdentifiedChannelEnd::new(
+                        PortId::from_str("icahost").unwrap(),
+                        ChannelId::new(1),
+                        channel_end(State::Init, None),
+                    ),
+                ];
+                reply_to.send(Ok(channels)).unwrap();
+            }
+        });
+
+        assert_eq!(
+            connection_channel_ids(&chain, &ConnectionId::new(2)).unwrap(),
+            vec![
+                (PortId::transfer(), ChannelId::new(0)),
+                (PortId::from_str("icahost").unwrap(), ChannelId::new(1)),
+            ]
+        );
+
+        let e = connection_channel_ids(&test_handle("chain-a"), &ConnectionId::new(2))
+            .expect_err("expected the query to fail");
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::Query(_)),
+            "unexpected error: {e}"
+        );
+    }
+
+    #[test]
+    fn open_channels_are_found_in_channel_number_order() {
+        let transfer = PortId::transfer();
+        let other = PortId::from_str("other").unwrap();
+
+        let open = |channel: u64, counterparty: u64| {
+            IdentifiedChannelEnd::new(
+                transfer.clone(),
+                ChannelId::new(channel),
+                channel_end(State::Open, Some(ChannelId::new(counterparty))),
+            )
+        };
+
+        let mut ordered = channel_end(State::Open, Some(ChannelId::new(4)));
+        ordered.ordering = Order::Ordered;
+
+        let channels = vec![
+            open(10, 20),
+            IdentifiedChannelEnd::new(other, ChannelId::new(1), channel_end(State::Open, None)),
+            IdentifiedChannelEnd::new(transfer.clone(), ChannelId::new(3), ordered),
+            IdentifiedChannelEnd::new(
+                transfer.clone(),
+                ChannelId::new(5),
+                channel_end(State::TryOpen, Some(ChannelId::new(6))),
+            ),
+            IdentifiedChannelEnd::new(
+                transfer.clone(),
+                ChannelId::new(7),
+                channel_end(State::Open, None),
+            ),
+            open(2, 8),
+        ];
+
+        // `channel-10` comes after `channel-2`, even though it sorts before as a string
+        assert_eq!(
+            find_open_channels(&channels, &transfer, &transfer, Order::Unordered),
+            vec![
+                (ChannelId::new(2), ChannelId::new(8)),
+                (ChannelId::new(10), ChannelId::new(20)),
+            ]
+        );
+        assert_eq!(
+            find_open_channels(&channels, &transfer, &transfer, Order::Ordered),
+            vec![(ChannelId::new(3), ChannelId::new(4))]
+        );
+        assert!(
+            find_open_channels(&channels[1..5], &transfer, &transfer, Order::Unordered).is_empty()
+        );
+    }
+
+    #[test]
+    fn versions_compatibility() {
+        let ics20 = Version::ics20();
+        let fee = Version::ics20_with_fee();
+
+        // Empty versions are not known yet
+        assert!(versions_compatible(&Version::empty(), &fee));
+        assert!(versions_compatible(&ics20, &Version::empty()));
+
+        assert!(versions_compatible(&ics20, &ics20));
+        assert!(versions_compatible(&fee, &fee));
+        assert!(!versions_compatible(
+            &ics20,
+            &Version::new("ics20-2".to_string())
+        ));
+        assert!(!versions_compatible(&ics20, &fee));
+        assert!(!versions_compatible(&fee, &ics20));
+
+        // JSON versions are compared regardless of their formatting
+        let reordered =
+            Version::new(r#"{ "app_version": "ics20-1", "fee_version": "ics29-1" }"#.to_string());
+        assert!(versions_compatible(&reordered, &fee));
+
+        // and on their `version` field when they have one
+        let ica = Version::new(
+            r#"{"version":"ics27-1","encoding":"proto3","tx_type":"sdk_multi_msg"}"#.to_string(),
+        );
+        let ica_with_address = Version::new(
+            r#"{"version":"ics27-1","encoding":"proto3","tx_type":"sdk_multi_msg","address":"cosmos1"}"#
+                .to_string(),
+        );
+        let ica_next = Version::new(r#"{"version":"ics27-2"}"#.to_string());
+        assert!(versions_compatible(&ica_with_address, &ica));
+        assert!(!versions_compatible(&ica_next, &ica));
+    }
+
+    #[test]
+    fn proposed_version_prefers_explicit_destination_version() {
+        let fee = Version::ics20_with_fee();
+        let ics20 = Version::ics20();
+
+        // Without any explicit version, the counterparty version is re-used
+        assert_eq!(proposed_version(None, None, &ics20).unwrap(), ics20);
+        assert_eq!(proposed_version(Some(&fee), None, &ics20).unwrap(), ics20);
+
+        // An explicit version for the destination end takes precedence
+        assert_eq!(proposed_version(None, Some(&fee), &ics20).unwrap(), fee);
+        assert_eq!(
+            proposed_version(Some(&fee), Some(&fee), &ics20).unwrap(),
+            fee
+        );
+
+        let err = proposed_version(Some(&ics20), Some(&fee), &ics20)
+            .expect_err("expected incompatible versions");
+        assert!(matches!(
+            err.detail(),
+            ChannelErrorDetail::IncompatibleVersions(_)
+        ));
+    }
+
+    #[test]
+    fn handshake_step_from_states() {
+        use State::*;
+
+        let cases = [
+            (Uninitialized, Uninitialized, Some(HandshakeStep::Init)),
+            (Init, Uninitialized, Some(HandshakeStep::Try)),
+            (Uninitialized, Init, Some(HandshakeStep::Try)),
+            (Init, Init, Some(HandshakeStep::Try)),
+            (Init, TryOpen, Some(HandshakeStep::Ack)),
+            (TryOpen, Init, Some(HandshakeStep::Ack)),
+            (TryOpen, TryOpen, Some(HandshakeStep::Ack)),
+            (Open, TryOpen, Some(HandshakeStep::Confirm)),
+            (TryOpen, Open, Some(HandshakeStep::Confirm)),
+            (Open, Open, Some(HandshakeStep::Open)),
+            (Uninitialized, Open, None),
+            (Closed, Open, None),
+        ];
+
+        for (a_state, b_state, expected) in cases {
+            assert_eq!(
+                HandshakeStep::from_states(a_state, b_state),
+                expected,
+                "states {a_state}, {b_state}"
+            );
+        }
+
+        assert!(HandshakeStep::Init < HandshakeStep::Try);
+        assert!(HandshakeStep::Confirm < HandshakeStep::Open);
+    }
+
+    #[test]
+    fn handshake_steps_are_sent_to_the_end_which_is_behind() {
+        use State::*;
+
+        let cases = [
+            (Uninitialized, Uninitialized, ChannelEndSide::A),
+            (Init, Uninitialized, ChannelEndSide::B),
+            (Uninitialized, Init, ChannelEndSide::A),
+            (Init, Init, ChannelEndSide::A),
+            (Init, TryOpen, ChannelEndSide::A),
+            (TryOpen, Init, ChannelEndSide::B),
+            (TryOpen, TryOpen, ChannelEndSide::A),
+            (Open, TryOpen, ChannelEndSide::B),
+            (TryOpen, Open, ChannelEndSide::A),
+        ];
+
+        for (a_state, b_state, expected) in cases {
+            let step = HandshakeStep::from_states(a_state, b_state).unwrap();
+            assert_eq!(
+                step.destination(a_state, b_state),
+                expected,
+                "states {a_state}, {b_state}"
+            );
+        }
+    }
+
+    #[test]
+    fn handshake_steps_parse_from_their_names() {
+        for step in [
+            HandshakeStep::Init,
+            HandshakeStep::Try,
+            HandshakeStep::Ack,
+            HandshakeStep::Confirm,
+        ] {
+            assert_eq!(step.to_string().parse::<HandshakeStep>().unwrap(), step);
+        }
+
+        assert_eq!("ack".parse::<HandshakeStep>().unwrap(), HandshakeStep::Ack);
+        assert_eq!("TRY".parse::<HandshakeStep>().unwrap(), HandshakeStep::Try);
+
+        let e = "close".parse::<HandshakeStep>().unwrap_err();
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::UnknownHandshakeStep(_)),
+            "unexpected error: {e}"
+        );
+    }
+
+    #[test]
+    fn known_channel_ids_skip_the_init_step() {
+        let first_step = |a_id, b_id| test_channel(a_id, b_id).first_handshake_step();
+
+        assert_eq!(first_step(None, None), HandshakeStep::Init);
+        assert_eq!(
+            first_step(Some(ChannelId::new(0)), None),
+            HandshakeStep::Try
+        );
+        assert_eq!(
+            first_step(None, Some(ChannelId::new(1))),
+            HandshakeStep::Try
+        );
+    }
+
+    #[test]
+    fn query_status_of_unknown_ends() {
+        let status = test_channel(None, None).query_status().unwrap();
+        assert_eq!(status, (State::Uninitialized, State::Uninitialized));
+
+        // The chains are unreachable, the query of a known end fails cleanly
+        assert!(test_channel(Some(ChannelId::new(0)), None)
+            .query_status()
+            .is_err());
+    }
+
+    #[test]
+    fn resuming_an_open_handshake_verifies_both_ends() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        channel.a_side.chain = mock_handle("chain-a", ends_in_state(State::Open));
+        channel.b_side.chain = mock_handle("chain-b", ends_in_state(State::Open));
+
+        let result = channel.resume_handshake(HandshakeStep::Open).unwrap();
+        assert_eq!(result.step, HandshakeStep::Open);
+        assert_eq!(result.a_channel_id, Some(ChannelId::new(0)));
+        assert_eq!(result.b_channel_id, Some(ChannelId::new(1)));
+        assert!(result.events.is_empty());
+        assert!(result.already_open);
+
+        // An end which is not open yet must not be reported as open
+        channel.b_side.chain = mock_handle("chain-b", ends_in_state(State::TryOpen));
+        let e = channel.resume_handshake(HandshakeStep::Open).unwrap_err();
+        assert!(
+            matches!(e.detail(), ChannelErrorDetail::ChannelNotOpen(_)),
+            "unexpected error: {e}"
+        );
+
+        // The channel ids are required to check the ends
+        assert!(test_channel(Some(ChannelId::new(0)), None)
+            .resume_handshake(HandshakeStep::Open)
+            .is_err());
+    }
+
+    #[test]
+    fn resuming_a_handshake_acts_on_the_states_of_the_ends() {
+        use State::*;
+
+        #[derive(Debug, PartialEq)]
+        enum Outcome {
+            /// Both ends are open, nothing is sent
+            Open,
+            /// The message of the step is built and sent
+            Sends(HandshakeStep),
+            /// The ends are behind the step resumed from, or in states which cannot
+            /// occur during a handshake: nothing is sent and the step is retried
+            Waits,
+        }
+
+        let cases = [
+            (
+                Init,
+                Init,
+                HandshakeStep::Try,
+                Outcome::Sends(HandshakeStep::Try),
+            ),
+            (
+                Init,
+                TryOpen,
+                HandshakeStep::Try,
+                Outcome::Sends(HandshakeStep::Ack),
+            ),
+            (
+                TryOpen,
+                Init,
+                HandshakeStep::Try,
+                Outcome::Sends(HandshakeStep::Ack),
+            ),
+            (
+                TryOpen,
+                TryOpen,
+                HandshakeStep::Try,
+                Outcome::Sends(HandshakeStep::Ack),
+            ),
+            (
+                Open,
+                TryOpen,
+                HandshakeStep::Try,
+                Outcome::Sends(HandshakeStep::Confirm),
+            ),
+            (
+                TryOpen,
+                Open,
+                HandshakeStep::Try,
+                Outcome::Sends(HandshakeStep::Confirm),
+            ),
+            (Open, Open, HandshakeStep::Try, Outcome::Open),
+            (Init, Open, HandshakeStep::Try, Outcome::Waits),
+            (Open, Init, HandshakeStep::Try, Outcome::Waits),
+            (Init, Init, HandshakeStep::Ack, Outcome::Waits),
+            (Init, TryOpen, HandshakeStep::Confirm, Outcome::Waits),
+            (
+                TryOpen,
+                TryOpen,
+                HandshakeStep::Ack,
+                Outcome::Sends(HandshakeStep::Ack),
+            ),
+            (
+                Open,
+                TryOpen,
+                HandshakeStep::Confirm,
+                Outcome::Sends(HandshakeStep::Confirm),
+            ),
+            (Open, Open, HandshakeStep::Confirm, Outcome::Open),
+        ];
+
+        for (a_state, b_state, from, expected) in cases {
+            let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+            channel.a_side.chain = mock_handle("chain-a", ends_in_state(a_state));
+            channel.b_side.chain = mock_handle("chain-b", ends_in_state(b_state));
+
+            let mut progress = HandshakeResult::new(&channel, from);
+            let result = channel.do_chan_open_handshake(from, &mut progress, &|_| {});
+
+            // The mock chains answer the queries of the channel ends only, so the
+            // message of a step fails to be built once the relayer goes on to send it
+            let outcome = match result {
+                Ok(()) => Outcome::Open,
+                Err(e) if matches!(e.detail(), ChannelErrorDetail::HandshakeFinalize(_)) => {
+                    Outcome::Waits
+                }
+                Err(_) => Outcome::Sends(progress.step),
+            };
+
+            assert_eq!(outcome, expected, "states {a_state}, {b_state} from {from}");
+            assert_eq!(progress.states, Some((a_state, b_state)));
+            assert!(progress.events.is_empty());
+            assert_eq!(progress.already_open, expected == Outcome::Open);
+        }
+    }
+
+    #[test]
+    fn handshakes_give_up_at_the_overall_deadline() {
+        // Chains which are quick to retry on, and whose channel ends are both in state
+        // `Init`, on which `ChanOpenTry` fails to be built
+        let respond = |state| {
+            let ends = ends_in_state(state);
+            move |request| match request {
+                ChainRequest::Config { reply_to } => {
+                    let mut config = test_chain_config();
+                    config.max_block_time = Duration::from_millis(10);
+                    config.handshake_retry.max_attempts = Some(3);
+                    reply_to.send(Ok(config)).unwrap();
+                }
+                request => ends(request),
+            }
+        };
+
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        channel.a_side.chain = mock_handle("chain-a", respond(State::Init));
+        channel.b_side.chain = mock_handle("chain-b", respond(State::Init));
+
+        let e = channel.handshake().unwrap_err();
+        match e.detail() {
+            ChannelErrorDetail::HandshakeTimedOut(e) => assert_eq!(e.attempts, 3),
+            _ => panic!("unexpected error: {e}"),
+        }
+
+        // The step is not retried past the deadline
+        channel.overall_deadline = Some(Instant::now());
+
+        let e = channel.handshake().unwrap_err();
+        match e.detail() {
+            ChannelErrorDetail::HandshakeTimedOut(e) => {
+                assert_eq!(e.attempts, 1);
+                assert_eq!(e.progress.step, HandshakeStep::Try);
+                assert_eq!(e.progress.last_state, Some((State::Init, State::Init)));
+            }
+            _ => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn ack_requires_the_requested_version() {
+        let fee = Version::ics20_with_fee();
+        let ics20 = Version::ics20();
+
+        assert!(validate_channel_version(None, &ics20).is_ok());
+        assert!(validate_channel_version(Some(&Version::empty()), &ics20).is_ok());
+        assert!(validate_channel_version(Some(&ics20), &ics20).is_ok());
+
+        let e = validate_channel_version(Some(&fee), &ics20).expect_err("expected a mismatch");
+        assert!(matches!(
+            e.detail(),
+            ChannelErrorDetail::IncompatibleVersions(_)
+        ));
+        assert!(e.to_string().contains(&fee.to_string()));
+    }
+
+    #[test]
+    fn builders_fail_cleanly_on_missing_ids() {
+        let channel = test_channel(None, None);
+
+        assert_missing_local(channel.build_chan_open_try());
+        assert_missing_local(channel.build_chan_open_ack());
+        assert_missing_local(channel.build_chan_open_confirm());
+        assert_missing_local(channel.build_chan_close_confirm());
+        assert_missing_counterparty(channel.build_chan_close_init());
+
+        let channel = test_channel(Some(ChannelId::new(0)), None);
+
+        assert_missing_counterparty(channel.build_chan_open_ack());
+        assert_missing_counterparty(channel.build_chan_open_confirm());
+        assert_missing_counterparty(channel.build_chan_close_confirm());
+    }
+
+    #[test]
+    fn handshake_step_events_are_extracted_from_the_response() {
+        let open_init = IbcEvent::from(OpenInit {
+            port_id: PortId::transfer(),
+            channel_id: Some(ChannelId::new(0)),
+            connection_id: ConnectionId::default(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: None,
+        });
+
+        let respond_with = |events: Vec<IbcEvent>| {
+            mock_handle("chain-a", move |request: ChainRequest| {
+                if let ChainRequest::SendMessagesAndWaitCommit { reply_to, .. } = request {
+                    let height = Height::new(0, 10).unwrap();
+                    let events = events
+                        .iter()
+                        .cloned()
+                        .map(|event| IbcEventWithHeight::new(event, height))
+                        .collect();
+                    reply_to.send(Ok(events)).unwrap();
+                }
+            })
+        };
+        let is_open_init = |event: &IbcEvent| matches!(event, IbcEvent::OpenInitChannel(_));
+
+        // The chain error is reported as it comes before the event of the step
+        let chain = respond_with(vec![
+            IbcEvent::ChainError(ChainError::default()),
+            open_init.clone(),
+        ]);
+        let e = send_and_extract(&chain, vec![], "ChannelOpenInit", is_open_init)
+            .expect_err("expected the chain error");
+        assert!(matches!(e.detail(), ChannelErrorDetail::TxResponse(_)));
+
+        let chain = respond_with(vec![open_init, IbcEvent::ChainError(ChainError::default())]);
+        let result = send_and_extract(&chain, vec![], "ChannelOpenInit", is_open_init).unwrap();
+        assert_eq!(
+            extract_channel_id(&result.event).unwrap(),
+            &ChannelId::new(0)
+        );
+
+        let chain = respond_with(vec![]);
+        let e = send_and_extract(&chain, vec![], "ChannelOpenInit", is_open_init)
+            .expect_err("expected a missing event");
+        assert!(matches!(e.detail(), ChannelErrorDetail::MissingEvent(_)));
+
+        let chain = respond_with(vec![IbcEvent::NewBlock(NewBlock::new(
+            Height::new(0, 10).unwrap(),
+        ))]);
+        let e = send_and_extract(&chain, vec![], "ChannelOpenInit", is_open_init)
+            .expect_err("expected an unexpected event");
+        match e.detail() {
+            ChannelErrorDetail::UnexpectedEvent(e) => {
+                assert_eq!(e.msg_type, "ChannelOpenInit");
+                assert!(matches!(e.event, IbcEvent::NewBlock(_)));
+            }
+            _ => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn batched_msgs_share_client_updates_and_keep_their_order() {
+        let any = |type_url: &str| Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        };
+        // Updates of a client to the height of the dummy header, which differ in
+        // the height they are trusted from
+        let update = |client: u64, trusted: u64| {
+            let mut header = get_dummy_ics07_header();
+            header.trusted_height = Height::new(0, trusted).unwrap();
+            MsgUpdateClient::new(
+                ClientId::new(ClientType::Tendermint, client).unwrap(),
+                AnyHeader::Tendermint(header).into(),
+                Signer::from_str("cosmos1relayer").unwrap(),
+            )
+            .to_any()
+        };
+        let open_init = |channel_id: u64| {
+            IbcEvent::from(OpenInit {
+                port_id: PortId::transfer(),
+                channel_id: Some(ChannelId::new(channel_id)),
+                connection_id: ConnectionId::default(),
+                counterparty_port_id: PortId::transfer(),
+                counterparty_channel_id: None,
+            })
+        };
+
+        let height = Height::new(0, 10).unwrap();
+        let events = vec![
+            IbcEventWithHeight::new(open_init(0), height),
+            IbcEventWithHeight::new(open_init(1), height),
+        ];
+        let (chain, msgs) = recording_handle("chain-a", events, |_| {});
+
+        let results = send_batched_msgs(
+            &chain,
+            vec![
+                Ok(vec![update(0, 1), any("init-0")]),
+                Err(ChannelError::invalid_config("test".to_string())),
+                Ok(vec![update(0, 2), update(1, 1), any("init-1")]),
+            ],
+            "ChannelOpenInit",
+            |_, event| matches!(event, IbcEvent::OpenInitChannel(_)),
+        );
+
+        // The update of client 0 to the same height is only submitted once
+        assert_eq!(
+            msgs.recv().unwrap(),
+            vec![update(0, 1), any("init-0"), update(1, 1), any("init-1")]
+        );
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            extract_channel_id(results[0].as_ref().unwrap()).unwrap(),
+            &ChannelId::new(0)
+        );
+        assert!(matches!(
+            results[1].as_ref().unwrap_err().detail(),
+            ChannelErrorDetail::InvalidConfig(_)
+        ));
+        assert_eq!(
+            extract_channel_id(results[2].as_ref().unwrap()).unwrap(),
+            &ChannelId::new(1)
+        );
+    }
+
+    #[test]
+    fn channels_leaving_the_batch_complete_their_handshakes_concurrently() {
+        let open_ends = || {
+            let ends = ends_in_state(State::Open);
+            move |request| match request {
+                ChainRequest::Config { reply_to } => {
+                    reply_to.send(Ok(test_chain_config())).unwrap();
+                }
+                request => ends(request),
+            }
+        };
+
+        // Answers as a chain whose channel ends are open, once the chains of both
+        // unbatched channels were queried, so that one handshake cannot complete
+        // before the other one started
+        let started = Arc::new((std::sync::Mutex::new(0), std::sync::Condvar::new()));
+        let both_started = |chain_id: &str| {
+            let started = started.clone();
+            let ends = open_ends();
+            let waiting = std::sync::Mutex::new(true);
+            mock_handle(chain_id, move |request| {
+                if std::mem::take(&mut *waiting.lock().unwrap()) {
+                    let (count, changed) = &*started;
+                    *count.lock().unwrap() += 1;
+                    changed.notify_all();
+                    let (count, _) = changed
+                        .wait_timeout_while(
+                            count.lock().unwrap(),
+                            Duration::from_secs(10),
+                            |count| *count < 2,
+                        )
+                        .unwrap();
+                    if *count < 2 {
+                        // Drop the reply so that the handshake fails
+                        return;
+                    }
+                }
+                ends(request)
+            })
+        };
+
+        let unbatched_channel = |a_chain_id: &str| {
+            let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+            channel.a_side.chain = both_started(a_chain_id);
+            channel.b_side.chain = mock_handle("chain-b", open_ends());
+            channel
+        };
+
+        let results = Channel::complete_unbatched(
+            vec![
+                Ok(unbatched_channel("chain-a")),
+                // Left as is, its chains failing every request
+                Ok(test_channel(
+                    Some(ChannelId::new(2)),
+                    Some(ChannelId::new(3)),
+                )),
+                Err(ChannelError::invalid_config("test".to_string())),
+                Ok(unbatched_channel("chain-c")),
+            ],
+            vec![true, false, false, true],
+        );
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0].as_ref().unwrap().a_chain().id(),
+            ChainId::from_string("chain-a")
+        );
+        assert_eq!(
+            results[1].as_ref().unwrap().a_channel_id(),
+            Some(&ChannelId::new(2))
+        );
+        assert!(matches!(
+            results[2].as_ref().unwrap_err().detail(),
+            ChannelErrorDetail::InvalidConfig(_)
+        ));
+        assert_eq!(
+            results[3].as_ref().unwrap().a_chain().id(),
+            ChainId::from_string("chain-c")
+        );
+    }
+
+    #[test]
+    fn pending_packets_are_sent_by_a_and_unreceived_on_b() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        let height = Height::new(0, 10).unwrap();
+
+        channel.a_side.chain = mock_handle("chain-a", move |request| match request {
+            ChainRequest::QueryPacketCommitments { request, reply_to } => {
+                assert_eq!(request.channel_id, ChannelId::new(0));
+                reply_to
+                    .send(Ok((vec![1.into(), 2.into()], height)))
+                    .unwrap();
+            }
+            ChainRequest::QueryPacketEventData { request, reply_to } => {
+                assert_eq!(request.sequences, vec![1.into()]);
+                let event = IbcEvent::SendPacket(SendPacket {
+                    packet: test_packet(),
+                });
+                reply_to
+                    .send(Ok(vec![IbcEventWithHeight::new(event, height)]))
+                    .unwrap();
+            }
+            _ => {}
+        });
+        channel.b_side.chain = mock_handle("chain-b", |request| {
+            if let ChainRequest::QueryUnreceivedPackets { request, reply_to } = request {
+                assert_eq!(request.channel_id, ChannelId::new(1));
+                assert_eq!(
+                    request.packet_commitment_sequences,
+                    vec![1.into(), 2.into()]
+                );
+                reply_to.send(Ok(vec![1.into()])).unwrap();
+            }
+        });
+
+        let pending = channel.scan_pending_packets().unwrap();
+
+        assert_eq!(pending, vec![PendingPacket::from(test_packet())]);
+    }
+
+    #[test]
+    fn dry_run_messages_are_the_submitted_ones() {
+        let mut channel = test_channel(None, None);
+        let height = Height::new(0, 10).unwrap();
+
+        let respond = move |request| match request {
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                let status = ChainStatus {
+                    height,
+                    timestamp: Timestamp::none(),
+                };
+                reply_to.send(Ok(status)).unwrap();
+            }
+            ChainRequest::Signer { reply_to } => {
+                let signer = Signer::from_str("cosmos1relayer").unwrap();
+                reply_to.send(Ok(signer)).unwrap();
+            }
+            ChainRequest::QueryConnectionChannels { reply_to, .. } => {
+                reply_to.send(Ok(vec![])).unwrap();
+            }
+            _ => {}
+        };
+        let event = IbcEvent::from(OpenInit {
+            port_id: PortId::transfer(),
+            channel_id: Some(ChannelId::new(0)),
+            connection_id: ConnectionId::default(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: None,
+        });
+        let events = vec![IbcEventWithHeight::new(event, height)];
+        let (a_chain, msgs) = recording_handle("chain-a", events, respond);
+        channel.a_side.chain = a_chain;
+        channel.b_side.chain = mock_handle("chain-b", respond);
+
+        let planned = channel.build_chan_open_messages().unwrap();
+        channel.flipped().build_chan_open_init_and_send().unwrap();
+
+        assert_eq!(planned.step, HandshakeStep::Init);
+        assert_eq!(planned.chain_id, ChainId::from_string("chain-a"));
+        assert_eq!(planned.messages, msgs.recv().unwrap());
+    }
+
+    #[test]
+    fn all_open_init_events_of_the_response_are_returned() {
+        let mut channel = test_channel(None, None);
+        let height = Height::new(0, 10).unwrap();
+
+        let open_init = |channel_id: u64, port_id: &str| {
+            IbcEvent::from(OpenInit {
+                port_id: PortId::from_str(port_id).unwrap(),
+                channel_id: Some(ChannelId::new(channel_id)),
+                connection_id: ConnectionId::default(),
+                counterparty_port_id: PortId::transfer(),
+                counterparty_channel_id: None,
+            })
+        };
+        let respond = move |request| match request {
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                let status = ChainStatus {
+                    height,
+                    timestamp: Timestamp::none(),
+                };
+                reply_to.send(Ok(status)).unwrap();
+            }
+            ChainRequest::Signer { reply_to } => {
+                let signer = Signer::from_str("cosmos1relayer").unwrap();
+                reply_to.send(Ok(signer)).unwrap();
+            }
+            ChainRequest::SendMessagesAndWaitCommit { reply_to, .. } => {
+                let events = vec![
+                    IbcEventWithHeight::new(open_init(0, "transfer"), height),
+                    IbcEventWithHeight::new(open_init(1, "icahost"), height),
+                ];
+                reply_to.send(Ok(events)).unwrap();
+            }
+            _ => {}
+        };
+        channel.a_side.chain = mock_handle("chain-a", respond);
+        channel.b_side.chain = mock_handle("chain-b", respond);
+
+        let events = channel.build_chan_open_init_and_send_all().unwrap();
+        let channel_ids: Vec<&ChannelId> = events
+            .iter()
+            .map(|event| extract_channel_id(event).unwrap())
+            .collect();
+        assert_eq!(channel_ids, vec![&ChannelId::new(0), &ChannelId::new(1)]);
+
+        // The single message path takes the first event
+        let event = channel.build_chan_open_init_and_send().unwrap();
+        assert_eq!(extract_channel_id(&event).unwrap(), &ChannelId::new(0));
+    }
+
+    #[test]
+    fn saved_channel_ids_are_resumed_by_the_same_channel_only() {
+        let path =
+            std::env::temp_dir().join(format!("hermes-channel-state-{}.json", std::process::id()));
+
+        let saved = test_channel(Some(ChannelId::new(3)), Some(ChannelId::new(7)));
+        saved.save_state(&path).unwrap();
+
+        let mut other = test_channel(None, None);
+        other.b_side.port_id = PortId::from_str("icahost").unwrap();
+        assert!(!other.load_state(&path).unwrap());
+        assert_eq!(other.b_channel_id(), None);
+
+        let mut resumed = test_channel(None, None);
+        let loaded = resumed.load_state(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(loaded.unwrap());
+        assert_eq!(resumed.a_channel_id(), Some(&ChannelId::new(3)));
+        assert_eq!(resumed.b_channel_id(), Some(&ChannelId::new(7)));
+    }
+
+    #[test]
+    fn channels_require_an_open_connection_on_both_chains() {
+        use ibc_relayer_types::core::ics03_connection::connection::State as ConnectionState;
+
+        let mut channel = test_channel(None, None);
+        channel.a_side.chain = mock_handle("chain-a", connection_with_state(ConnectionState::Open));
+        channel.b_side.chain = mock_handle("chain-b", connection_with_state(ConnectionState::Open));
+        assert!(channel.check_connection_open().is_ok());
+
+        channel.b_side.chain = mock_handle("chain-b", connection_with_state(ConnectionState::Init));
+        let e = channel.check_connection_open().unwrap_err();
+        match e.detail() {
+            ChannelErrorDetail::ConnectionNotOpen(e) => {
+                assert_eq!(e.chain_id, ChainId::from_string("chain-b"));
+                assert_eq!(&e.connection_id, channel.b_side.connection_id());
+                assert_eq!(e.state, ConnectionState::Init);
+            }
+            _ => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn step_reports_completion_and_lagging_chains() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        channel.a_side.chain = mock_handle("chain-a", ends_in_state(State::Open));
+        channel.b_side.chain = mock_handle("chain-b", ends_in_state(State::Open));
+        assert!(matches!(channel.step().unwrap(), ChannelStep::Done));
+
+        // The channel ids are known, the chains must catch up rather than open another channel
+        channel.a_side.chain = mock_handle("chain-a", ends_in_state(State::Uninitialized));
+        channel.b_side.chain = mock_handle("chain-b", ends_in_state(State::Uninitialized));
+        assert!(matches!(
+            channel.step().unwrap(),
+            ChannelStep::NeedsRetry(_)
+        ));
+    }
+
+    #[test]
+    fn open_ack_carries_the_version_of_the_source_channel() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        let icahost = PortId::from_str("icahost").unwrap();
+        channel.b_side.set_port_id(&icahost);
+        let height = Height::new(0, 10).unwrap();
+        let client_height = Height::new(0, 5).unwrap();
+
+        channel.a_side.chain = mock_handle("chain-a", move |request| match request {
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                let status = ChainStatus {
+                    height,
+                    timestamp: Timestamp::none(),
+                };
+                reply_to.send(Ok(status)).unwrap();
+            }
+            ChainRequest::QueryChannel {
+                request, reply_to, ..
+            } => {
+                assert_eq!(request.port_id, PortId::transfer());
+                let end = ChannelEnd::new(
+                    State::TryOpen,
+                    Order::Unordered,
+                    Counterparty::new(
+                        PortId::from_str("icahost").unwrap(),
+                        Some(ChannelId::new(1)),
+                    ),
+                    vec![ConnectionId::default()],
+                    Version::ics20(),
+                );
+                reply_to.send(Ok((end, None))).unwrap();
+            }
+            ChainRequest::BuildChannelProofs {
+                height, reply_to, ..
+            } => {
+                let proof = CommitmentProofBytes::try_from(vec![1]).unwrap();
+                let proofs = Proofs::new(proof, None, None, None, height).unwrap();
+                reply_to.send(Ok(proofs)).unwrap();
+            }
+            request => respond_as_client_source(request, height),
+        });
+        channel.b_side.chain = mock_handle("chain-b", move |request| match request {
+            ChainRequest::QueryChannel {
+                request, reply_to, ..
+            } => {
+                assert_eq!(request.port_id.as_str(), "icahost");
+                let end = ChannelEnd::new(
+                    State::Init,
+                    Order::Unordered,
+                    Counterparty::new(PortId::transfer(), None),
+                    vec![ConnectionId::default()],
+                    Version::empty(),
+                );
+                reply_to.send(Ok((end, None))).unwrap();
+            }
+            ChainRequest::QueryConnection { reply_to, .. } => {
+                reply_to.send(Ok((ConnectionEnd::default(), None))).unwrap();
+            }
+            ChainRequest::Config { reply_to } => {
+                reply_to.send(Ok(test_chain_config())).unwrap();
+            }
+            request => respond_as_client_host(request, client_height),
+        });
+
+        // The client is behind the proof height and is updated first
+        let msgs = channel.build_chan_open_ack().unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        let msg = MsgChannelOpenAck::decode_vec(&msgs[1].value).unwrap();
+        assert_eq!(msg.port_id, icahost);
+        assert_eq!(msg.counterparty_channel_id, ChannelId::new(0));
+        assert_eq!(msg.counterparty_version, Version::ics20());
+    }
+
+    #[test]
+    fn proofs_can_be_built_at_the_height_trusted_by_the_client() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        let latest_height = Height::new(0, 15).unwrap();
+        let client_height = Height::new(0, 10).unwrap();
+
+        // The source channel end is the same at every height
+        let src_channel = channel_end(State::Open, Some(ChannelId::new(1)));
+        channel.a_side.chain = mock_handle("chain-a", move |request| match request {
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                let status = ChainStatus {
+                    height: latest_height,
+                    timestamp: Timestamp::none(),
+                };
+                reply_to.send(Ok(status)).unwrap();
+            }
+            ChainRequest::QueryChannel { reply_to, .. } => {
+                reply_to.send(Ok((src_channel.clone(), None))).unwrap();
+            }
+            ChainRequest::BuildChannelProofs {
+                height, reply_to, ..
+            } => {
+                let proof = CommitmentProofBytes::try_from(vec![1]).unwrap();
+                let proofs = Proofs::new(proof, None, None, None, height.increment()).unwrap();
+                reply_to.send(Ok(proofs)).unwrap();
+            }
+            request => respond_as_client_source(request, latest_height),
+        });
+        channel.b_side.chain = mock_handle("chain-b", move |request| match request {
+            ChainRequest::QueryChannel { reply_to, .. } => {
+                let end = channel_end(State::TryOpen, Some(ChannelId::new(0)));
+                reply_to.send(Ok((end, None))).unwrap();
+            }
+            ChainRequest::QueryConnection { reply_to, .. } => {
+                reply_to.send(Ok((ConnectionEnd::default(), None))).unwrap();
+            }
+            ChainRequest::Config { reply_to } => {
+                let mut config = test_chain_config();
+                config.handshake_proofs_at_trusted_height = true;
+                reply_to.send(Ok(config)).unwrap();
+            }
+            request => respond_as_client_host(request, client_height),
+        });
+
+        // The proofs are verified against the consensus state of the client,
+        // so that no client update precedes the message
+        let msgs = channel.build_chan_open_confirm().unwrap();
+        assert_eq!(msgs.len(), 1);
+
+        let msg = MsgChannelOpenConfirm::decode_vec(&msgs[0].value).unwrap();
+        assert_eq!(msg.proofs.height(), client_height);
+    }
+
+    #[test]
+    fn open_try_can_be_built_from_an_observed_channel_end() {
+        let mut channel = test_channel(Some(ChannelId::new(0)), Some(ChannelId::new(1)));
+        let init_height = Height::new(0, 12).unwrap();
+        let client_height = Height::new(0, 5).unwrap();
+
+        // Chain a only builds proofs and headers, the source channel end is never queried
+        channel.a_side.chain = mock_handle("chain-a", move |request| match request {
+            ChainRequest::BuildChannelProofs {
+                height, reply_to, ..
+            } => {
+                let proof = CommitmentProofBytes::try_from(vec![1]).unwrap();
+                let proofs = Proofs::new(proof, None, None, None, height.increment()).unwrap();
+                reply_to.send(Ok(proofs)).unwrap();
+            }
+            request => respond_as_client_source(request, init_height.increment()),
+        });
+        channel.b_side.chain = mock_handle("chain-b", move |request| match request {
+            ChainRequest::QueryConnection { reply_to, .. } => {
+                reply_to.send(Ok((ConnectionEnd::default(), None))).unwrap();
+            }
+            request => respond_as_client_host(request, client_height),
+        });
+
+        let src_channel = ChannelEnd::new(
+            State::Init,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), None),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+        let msgs = channel
+            .build_chan_open_try_from_end(&src_channel, init_height)
+            .unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        let msg = MsgChannelOpenTry::decode_vec(&msgs[1].value).unwrap();
+        assert_eq!(msg.proofs.height(), init_height.increment());
+        assert_eq!(msg.counterparty_version, Version::ics20());
+        assert_eq!(msg.previous_channel_id, Some(ChannelId::new(1)));
+    }
+
+    #[test]
+    fn simultaneous_inits_converge_on_mock_chains() {
+        let init_height = Height::new(0, 12).unwrap();
+        let client_height = Height::new(0, 5).unwrap();
+
+        // Builds the proofs of the source end, as the chain of the source end
+        let source = |chain_id: &str| {
+            mock_handle(chain_id, move |request| match request {
+                ChainRequest::BuildChannelProofs {
+                    height, reply_to, ..
+                } => {
+                    let proof = CommitmentProofBytes::try_from(vec![1]).unwrap();
+                    let proofs = Proofs::new(proof, None, None, None, height.increment()).unwrap();
+                    reply_to.send(Ok(proofs)).unwrap();
+                }
+                request => respond_as_client_source(request, init_height.increment()),
+            })
+        };
+
+        // Hosts the given channels, as the chain of the destination end
+        let destination = |chain_id: &str, channels: Vec<IdentifiedChannelEnd>| {
+            mock_handle(chain_id, move |request| match request {
+                ChainRequest::QueryConnection { reply_to, .. } => {
+                    reply_to.send(Ok((ConnectionEnd::default(), None))).unwrap();
+                }
+                ChainRequest::QueryConnectionChannels { reply_to, .. } => {
+                    reply_to.send(Ok(channels.clone())).unwrap();
+                }
+                request => respond_as_client_host(request, client_height),
+            })
+        };
+
+        // Both chains sent `ChanOpenInit` concurrently: channel-3 on chain a and
+        // channel-7 on chain b, neither of them knowing about the other one yet.
+        // Chain b also has a channel opened with channel-1 of chain a.
+        let a_channels = vec![IdentifiedChannelEnd::new(
+            PortId::transfer(),
+            ChannelId::new(3),
+            channel_end(State::Init, None),
+        )];
+        let b_channels = vec![
+            IdentifiedChannelEnd::new(
+                PortId::transfer(),
+                ChannelId::new(5),
+                channel_end(State::Open, Some(ChannelId::new(1))),
+            ),
+            IdentifiedChannelEnd::new(
+                PortId::transfer(),
+                ChannelId::new(7),
+                channel_end(State::Init, None),
+            ),
+        ];
+
+        let build_try = |channel: &Channel<BaseChainHandle, BaseChainHandle>| {
+            let msgs = channel
+                .build_chan_open_try_from_end(&channel_end(State::Init, None), init_height)
+                .unwrap();
+            MsgChannelOpenTry::decode_vec(&msgs[msgs.len() - 1].value).unwrap()
+        };
+
+        // A relayer which initialized channel-3 moves channel-7 to `TryOpen` on chain b,
+        let mut channel = test_channel(Some(ChannelId::new(3)), None);
+        channel.a_side.chain = source("chain-a");
+        channel.b_side.chain = destination("chain-b", b_channels);
+
+        let msg = build_try(&channel);
+        assert_eq!(msg.previous_channel_id, Some(ChannelId::new(7)));
+        assert_eq!(
+            msg.channel.counterparty().channel_id(),
+            Some(&ChannelId::new(3))
+        );
+
+        // and one which initialized channel-7 moves channel-3 to `TryOpen` on chain a,
+        // so that the handshake completes with a single channel on each side
+        let mut channel = test_channel(None, Some(ChannelId::new(7)));
+        channel.a_side.chain = destination("chain-a", a_channels);
+        channel.b_side.chain = source("chain-b");
+
+        let msg = build_try(&channel.flipped());
+        assert_eq!(msg.previous_channel_id, Some(ChannelId::new(3)));
+        assert_eq!(
+            msg.channel.counterparty().channel_id(),
+            Some(&ChannelId::new(7))
+        );
     }
 }
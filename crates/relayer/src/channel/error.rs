@@ -1,16 +1,21 @@
 use core::time::Duration;
 
-use flex_error::{define_error, ErrorMessageTracer};
+use flex_error::{define_error, ErrorMessageTracer, TraceError};
 
 use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
-use ibc_relayer_types::core::ics04_channel::channel::State;
+use ibc_relayer_types::core::ics03_connection::connection::State as ConnectionState;
+use ibc_relayer_types::core::ics04_channel::channel::{Order, State};
+use ibc_relayer_types::core::ics04_channel::version::Version;
 use ibc_relayer_types::core::ics24_host::identifier::{
-    ChainId, ChannelId, ClientId, PortChannelId, PortId,
+    ChainId, ChannelId, ClientId, ConnectionId, PortChannelId, PortId,
 };
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ChainError, IbcEvent};
 
+use crate::channel::ChannelHandshakeProgress;
 use crate::error::Error as RelayerError;
 use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
+use crate::handshake::HandshakeStepError;
+use crate::sdk_error::is_permanent_tx_error;
 use crate::supervisor::Error as SupervisorError;
 
 define_error! {
@@ -118,6 +123,17 @@ define_error! {
                     e.port_channel_id, e.chain_id)
             },
 
+        IncompatibleState
+            {
+                channel_id: ChannelId,
+                expected: State,
+                found: State,
+            }
+            | e | {
+                format_args!("channel '{0}' is in state '{2}' which is past the expected state '{1}'",
+                    e.channel_id, e.expected, e.found)
+            },
+
         ChannelAlreadyExist
             { channel_id: ChannelId }
             |e| { format_args!("channel '{}' already exist in an incompatible state", e.channel_id) },
@@ -154,6 +170,60 @@ define_error! {
                     e.counterparty_channel_id)
             },
 
+        MismatchOrdering
+            {
+                chain_id: ChainId,
+                channel_id: ChannelId,
+                expected: Order,
+                actual: Order,
+            }
+            | e | {
+                format_args!("channel '{0}' on chain '{1}' has ordering '{3}' but ordering '{2}' was requested",
+                    e.channel_id, e.chain_id, e.expected, e.actual)
+            },
+
+        VersionMismatch
+            {
+                channel_id: ChannelId,
+                expected: Version,
+                found: Version,
+            }
+            | e | {
+                format_args!("channel '{0}' has version '{2}' but version '{1}' was expected",
+                    e.channel_id, e.expected, e.found)
+            },
+
+        IncompatibleVersions
+            {
+                src_version: Version,
+                dst_version: Version,
+            }
+            | e | {
+                format_args!("incompatible channel versions: '{}' for the source end and '{}' for the destination end",
+                    e.src_version, e.dst_version)
+            },
+
+        HandshakeStateIo
+            { path: String }
+            [ TraceError<std::io::Error> ]
+            | e | {
+                format_args!("failed to access the channel handshake state file '{}'", e.path)
+            },
+
+        HandshakeStateEncode
+            { path: String }
+            [ TraceError<serde_json::Error> ]
+            | e | {
+                format_args!("failed to encode the channel handshake state file '{}'", e.path)
+            },
+
+        HandshakeStateDecode
+            { path: String }
+            [ TraceError<serde_json::Error> ]
+            | e | {
+                format_args!("failed to decode the channel handshake state file '{}'", e.path)
+            },
+
         MissingEvent
             { description: String }
             | e | {
@@ -168,10 +238,20 @@ define_error! {
             },
 
         TxResponse
-            { reason: String }
+            {
+                chain_id: ChainId,
+                error: ChainError,
+            }
             | e | {
-                format_args!("tx response error: {}",
-                    e.reason)
+                format_args!("tx response error on chain '{}': {}",
+                    e.chain_id, e.error)
+            },
+
+        MissingChannelId
+            { event: IbcEvent }
+            | e | {
+                format_args!("cannot extract the channel id from event: {}",
+                    e.event)
             },
 
         InvalidEvent
@@ -181,24 +261,271 @@ define_error! {
                     e.event)
             },
 
+        UnexpectedEvent
+            {
+                chain_id: ChainId,
+                msg_type: String,
+                event: IbcEvent,
+            }
+            | e | {
+                format_args!("unexpected event in the response to {} on chain '{}': {:?}",
+                    e.msg_type, e.chain_id, e.event)
+            },
+
+        HandshakeTimedOut
+            {
+                attempts: u64,
+                progress: ChannelHandshakeProgress,
+            }
+            [ Self ]
+            | e | {
+                format_args!("channel handshake did not complete the {:?} step after {} attempts, {}",
+                    e.progress.step, e.attempts, e.progress)
+            },
+
+        ChannelNotOpen
+            {
+                chain_id: ChainId,
+                channel_id: ChannelId,
+                state: State,
+            }
+            | e | {
+                format_args!("channel '{0}' on chain '{1}' is in state '{2}' instead of being open",
+                    e.channel_id, e.chain_id, e.state)
+            },
+
+        MismatchConnectionHops
+            {
+                chain_id: ChainId,
+                channel_id: ChannelId,
+                expected: Vec<ConnectionId>,
+                actual: Vec<ConnectionId>,
+            }
+            | e | {
+                format_args!("channel '{0}' on chain '{1}' has connection hops {3:?} but {2:?} were expected",
+                    e.channel_id, e.chain_id, e.expected, e.actual)
+            },
+
+        SubscriptionClosed
+            { chain_id: ChainId }
+            | e | {
+                format_args!("the event subscription to chain {} was closed", e.chain_id)
+            },
+
+        InvalidConfig
+            { reason: String }
+            | e | {
+                format_args!("invalid channel configuration: {}", e.reason)
+            },
+
+        ConnectionNotOpen
+            {
+                chain_id: ChainId,
+                connection_id: ConnectionId,
+                state: ConnectionState,
+            }
+            | e | {
+                format_args!("connection {} on chain {} is in state {}, expected it to be open",
+                    e.connection_id, e.chain_id, e.state)
+            },
+
+        ClientExpiredOrFrozen
+            {
+                client_id: ClientId,
+                chain_id: ChainId,
+            }
+            [ ForeignClientError ]
+            | e | {
+                format_args!("client '{0}' hosted by chain '{1}' is expired or frozen, the channel handshake cannot proceed",
+                    e.client_id, e.chain_id)
+            },
+
+        UnknownHandshakeStep
+            { step: String }
+            | e | {
+                format_args!("unknown channel handshake step '{}', expected one of init, try, ack or confirm",
+                    e.step)
+            },
+
         MaxRetry
             {
                 description: String,
                 tries: u64,
                 total_delay: Duration,
+                elapsed: Duration,
             }
             [ Self ]
             | e | {
-                format_args!("error after maximum retry of {} and total delay of {}s: {}",
-                    e.tries, e.total_delay.as_secs(), e.description)
+                format_args!("error after {} attempts in {}s (total delay of {}s): {}",
+                    e.tries, e.elapsed.as_secs(), e.total_delay.as_secs(), e.description)
             },
     }
 }
 
+impl ChannelErrorDetail {
+    /// Returns the stable code identifying this kind of error, eg. `HERMES-CHAN-005`.
+    ///
+    /// The codes are numbered contiguously, new variants get the next code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Relayer(_) => "HERMES-CHAN-001",
+            Self::Supervisor(_) => "HERMES-CHAN-002",
+            Self::Client(_) => "HERMES-CHAN-003",
+            Self::InvalidChannel(_) => "HERMES-CHAN-004",
+            Self::MissingLocalChannelId(_) => "HERMES-CHAN-005",
+            Self::MissingLocalConnection(_) => "HERMES-CHAN-006",
+            Self::MissingCounterpartyChannelId(_) => "HERMES-CHAN-007",
+            Self::MissingCounterpartyConnection(_) => "HERMES-CHAN-008",
+            Self::MissingChannelOnDestination(_) => "HERMES-CHAN-009",
+            Self::ChannelProof(_) => "HERMES-CHAN-010",
+            Self::ClientOperation(_) => "HERMES-CHAN-011",
+            Self::FetchSigner(_) => "HERMES-CHAN-012",
+            Self::Query(_) => "HERMES-CHAN-013",
+            Self::ChainQuery(_) => "HERMES-CHAN-014",
+            Self::QueryChannel(_) => "HERMES-CHAN-015",
+            Self::Submit(_) => "HERMES-CHAN-016",
+            Self::HandshakeFinalize(_) => "HERMES-CHAN-017",
+            Self::PartialOpenHandshake(_) => "HERMES-CHAN-018",
+            Self::IncompleteChannelState(_) => "HERMES-CHAN-019",
+            Self::ChannelAlreadyExist(_) => "HERMES-CHAN-020",
+            Self::MismatchChannelEnds(_) => "HERMES-CHAN-021",
+            Self::MismatchPort(_) => "HERMES-CHAN-022",
+            Self::MissingEvent(_) => "HERMES-CHAN-023",
+            Self::RetryInternal(_) => "HERMES-CHAN-024",
+            Self::TxResponse(_) => "HERMES-CHAN-025",
+            Self::InvalidEvent(_) => "HERMES-CHAN-026",
+            Self::MaxRetry(_) => "HERMES-CHAN-027",
+            Self::MismatchOrdering(_) => "HERMES-CHAN-028",
+            Self::VersionMismatch(_) => "HERMES-CHAN-029",
+            Self::HandshakeStateIo(_) => "HERMES-CHAN-030",
+            Self::HandshakeStateEncode(_) => "HERMES-CHAN-031",
+            Self::HandshakeStateDecode(_) => "HERMES-CHAN-032",
+            Self::IncompatibleVersions(_) => "HERMES-CHAN-033",
+            Self::IncompatibleState(_) => "HERMES-CHAN-034",
+            Self::MissingChannelId(_) => "HERMES-CHAN-035",
+            Self::HandshakeTimedOut(_) => "HERMES-CHAN-036",
+            Self::ChannelNotOpen(_) => "HERMES-CHAN-037",
+            Self::MismatchConnectionHops(_) => "HERMES-CHAN-038",
+            Self::UnexpectedEvent(_) => "HERMES-CHAN-039",
+            Self::InvalidConfig(_) => "HERMES-CHAN-040",
+            Self::SubscriptionClosed(_) => "HERMES-CHAN-041",
+            Self::ConnectionNotOpen(_) => "HERMES-CHAN-042",
+            Self::ClientExpiredOrFrozen(_) => "HERMES-CHAN-043",
+            Self::UnknownHandshakeStep(_) => "HERMES-CHAN-044",
+        }
+    }
+
+    /// Returns a short hint on how to remediate this kind of error.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Self::Relayer(_) => "the relayer encountered an error while talking to a chain, check the nested error",
+            Self::Supervisor(_) => "the supervisor failed to resolve the channel, check the nested error",
+            Self::Client(_) => "the ICS02 client is invalid, check the client state on the host chain",
+            Self::InvalidChannel(_) => "the channel definition is invalid, check the ports, connection and version",
+            Self::MissingLocalChannelId(_) => "the source channel has not been created yet, run the channel open init step first",
+            Self::MissingLocalConnection(_) => "the connection is not open on the source chain, finish the connection handshake first",
+            Self::MissingCounterpartyChannelId(_) => "the counterparty channel has not been created yet, run the channel open try step first",
+            Self::MissingCounterpartyConnection(_) => "the connection is not open on the counterparty chain, finish the connection handshake first",
+            Self::MissingChannelOnDestination(_) => "the channel does not exist on the destination chain, check the channel identifier",
+            Self::ChannelProof(_) => "the channel proofs could not be built, check that the full node serves proofs at the queried height",
+            Self::ClientOperation(_) => "the client update failed, check that the client is neither expired nor frozen",
+            Self::FetchSigner(_) => "the signer could not be fetched, check the key configured for the chain",
+            Self::Query(_) => "a chain query failed, check the RPC and gRPC endpoints of the chain",
+            Self::ChainQuery(_) => "a chain query failed, check the RPC and gRPC endpoints of the chain",
+            Self::QueryChannel(_) => "the channel query failed, check the channel and port identifiers",
+            Self::Submit(_) => "the transaction could not be submitted, check the chain endpoints, the account balance and the gas settings",
+            Self::HandshakeFinalize(_) => "the handshake step did not complete yet and will be retried",
+            Self::PartialOpenHandshake(_) => "the channel is only partially open, complete the handshake with `hermes tx chan-open-*`",
+            Self::IncompleteChannelState(_) => "the channel has no counterparty channel id yet, complete the channel handshake",
+            Self::ChannelAlreadyExist(_) => "a channel with an incompatible state already exists, use a different channel or port",
+            Self::MismatchChannelEnds(_) => "the two channel ends do not point to each other, check the channel identifiers on both chains",
+            Self::MismatchPort(_) => "the destination port does not match the counterparty port of the source channel",
+            Self::MissingEvent(_) => "the transaction did not emit the expected event, check the transaction on the chain",
+            Self::RetryInternal(_) => "an internal error occurred while retrying, please report it",
+            Self::TxResponse(_) => "the chain rejected the transaction, check the reason in the error",
+            Self::InvalidEvent(_) => "the event cannot be used to build a channel, check the event type",
+            Self::MaxRetry(_) => "the operation still failed after the maximum number of retries, check the nested error",
+            Self::MismatchOrdering(_) => "the channel exists with a different ordering, request the same ordering or use another channel",
+            Self::VersionMismatch(_) => "the two channel ends disagree on the channel version, check the versions supported by the applications",
+            Self::HandshakeStateIo(_) => "the handshake state file cannot be read or written, check its path and permissions",
+            Self::HandshakeStateEncode(_) => "the handshake state cannot be encoded, please report it",
+            Self::HandshakeStateDecode(_) => "the handshake state file is malformed, fix or remove it",
+            Self::IncompatibleVersions(_) => "make sure that the requested versions are the same and supported by both applications",
+            Self::IncompatibleState(_) => "the channel end has already moved past this handshake step, use another channel",
+            Self::MissingChannelId(_) => "the chain emitted an unexpected event for the transaction, check the chain logs",
+            Self::HandshakeTimedOut(_) => "the handshake step still failed after the maximum number of retries or at the handshake deadline, check the nested error, then resume the handshake or tune `handshake_retry` and `handshake_timeout`",
+            Self::ChannelNotOpen(_) => "the channel is not open, it may not have completed its handshake or may have been closed",
+            Self::MismatchConnectionHops(_) => "the channel end is not built on the expected connection, check the channel identifiers on both chains",
+            Self::UnexpectedEvent(_) => "the chain emitted an event which this version of the relayer does not expect, please report it",
+            Self::InvalidConfig(_) => "the channel configuration is inconsistent, check the chains, ports and ordering of both ends",
+            Self::SubscriptionClosed(_) => "the event source of the chain stopped, check the chain runtime logs",
+            Self::ConnectionNotOpen(_) => "complete the connection handshake before opening a channel on top of it",
+            Self::ClientExpiredOrFrozen(_) => "an expired or frozen client cannot be updated anymore, recover it through governance or open the channel over a new client and connection",
+            Self::UnknownHandshakeStep(_) => "name the step after its message, eg. `try` for `ChanOpenTry`",
+        }
+    }
+}
+
+impl ChannelError {
+    /// Returns the stable code identifying this error, see [`ChannelErrorDetail::code`].
+    pub fn code(&self) -> &'static str {
+        self.detail().code()
+    }
+
+    /// Returns a short remediation hint for this error, see [`ChannelErrorDetail::hint`].
+    pub fn hint(&self) -> &'static str {
+        self.detail().hint()
+    }
+
+    /// Returns how far the channel opening handshake got before failing with this
+    /// error, for the handshake to be resumed rather than started over.
+    pub fn handshake_progress(&self) -> Option<&ChannelHandshakeProgress> {
+        match self.detail() {
+            ChannelErrorDetail::HandshakeTimedOut(e) => Some(&e.progress),
+            _ => None,
+        }
+    }
+}
+
+impl ChannelErrorDetail {
+    /// Whether the error was caused by a chain in read-only mode refusing
+    /// to submit a transaction, in which case retrying is pointless.
+    pub fn is_read_only_mode_error(&self) -> bool {
+        match self {
+            Self::Relayer(e) => e.source.is_read_only_mode_error(),
+            Self::Submit(e) => e.source.is_read_only_mode_error(),
+            Self::ClientOperation(e) => e.source.is_read_only_mode_error(),
+            Self::HandshakeTimedOut(e) => e.source.is_read_only_mode_error(),
+            Self::MaxRetry(e) => e.source.is_read_only_mode_error(),
+            _ => false,
+        }
+    }
+}
+
+impl ChannelError {
+    /// See [`ChannelErrorDetail::is_read_only_mode_error`].
+    pub fn is_read_only_mode_error(&self) -> bool {
+        self.detail().is_read_only_mode_error()
+    }
+
+    /// Whether the step which failed with this error may succeed when performed again.
+    /// Transactions rejected by the chain for a permanent reason, such as running out
+    /// of gas or targeting a port which does not exist, are not worth retrying.
+    pub fn is_retryable(&self) -> bool {
+        match self.detail() {
+            ChannelErrorDetail::TxResponse(e) => {
+                !is_permanent_tx_error(e.error.code, &e.error.codespace)
+            }
+            _ => true,
+        }
+    }
+}
+
 impl HasExpiredOrFrozenError for ChannelErrorDetail {
     fn is_expired_or_frozen_error(&self) -> bool {
         match self {
             Self::ClientOperation(e) => e.source.is_expired_or_frozen_error(),
+            Self::ClientExpiredOrFrozen(_) => true,
             _ => false,
         }
     }
@@ -209,3 +536,226 @@ impl HasExpiredOrFrozenError for ChannelError {
         self.detail().is_expired_or_frozen_error()
     }
 }
+
+impl HandshakeStepError for ChannelError {
+    fn should_retry(&self) -> bool {
+        !self.is_expired_or_frozen_error() && !self.is_read_only_mode_error() && self.is_retryable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::channel::HandshakeStep;
+
+    #[test]
+    fn permanent_tx_errors_are_not_retryable() {
+        let tx_error = |code: u32, codespace: &str| {
+            ChannelError::tx_response(
+                ChainId::default(),
+                ChainError {
+                    code,
+                    codespace: codespace.to_string(),
+                    ..ChainError::default()
+                },
+            )
+        };
+
+        // account sequence mismatch
+        assert!(tx_error(32, "sdk").is_retryable());
+        // out of gas, the gas estimate may differ on the next attempt
+        assert!(tx_error(11, "sdk").is_retryable());
+        // unauthorized
+        assert!(!tx_error(4, "sdk").is_retryable());
+        // port not found
+        assert!(!tx_error(3, "port").is_retryable());
+        // invalid channel state, e.g. when racing with another relayer
+        assert!(tx_error(3, "channel").is_retryable());
+
+        assert!(ChannelError::missing_event("event".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn read_only_refusals_are_recognized_on_every_send_path() {
+        let read_only = || RelayerError::read_only_mode(ChainId::default());
+
+        let errors = vec![
+            ChannelError::relayer(read_only()),
+            ChannelError::submit(ChainId::default(), read_only()),
+            ChannelError::client_operation(
+                ClientId::default(),
+                ChainId::default(),
+                ForeignClientError::client_update(
+                    ChainId::default(),
+                    "update".to_string(),
+                    read_only(),
+                ),
+            ),
+            ChannelError::max_retry(
+                "description".to_string(),
+                1,
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                ChannelError::submit(ChainId::default(), read_only()),
+            ),
+        ];
+
+        for error in errors {
+            assert!(error.is_read_only_mode_error(), "{}", error);
+            assert!(!error.should_retry(), "{}", error);
+        }
+
+        assert!(
+            !ChannelError::relayer(RelayerError::empty_response_proof()).is_read_only_mode_error()
+        );
+    }
+
+    #[test]
+    fn error_codes_are_well_formed_and_unique() {
+        let progress = || ChannelHandshakeProgress {
+            step: HandshakeStep::Try,
+            a_channel_id: Some(ChannelId::new(0)),
+            b_channel_id: None,
+            last_state: Some((State::Init, State::Uninitialized)),
+        };
+        let port_channel_id = || PortChannelId {
+            channel_id: ChannelId::default(),
+            port_id: PortId::transfer(),
+        };
+        let json_error = || serde_json::from_str::<u64>("").unwrap_err();
+        let foreign_client_error = || {
+            ForeignClientError::expired_or_frozen(
+                ClientId::default(),
+                ChainId::default(),
+                "frozen".to_string(),
+            )
+        };
+
+        // One error of every variant
+        let errors = vec![
+            ChannelError::relayer(RelayerError::empty_response_proof()),
+            ChannelError::supervisor(SupervisorError::no_chains_available()),
+            ChannelError::client(ClientError::implementation_specific()),
+            ChannelError::invalid_channel("reason".to_string()),
+            ChannelError::missing_local_channel_id(),
+            ChannelError::missing_local_connection(ChainId::default()),
+            ChannelError::missing_counterparty_channel_id(),
+            ChannelError::missing_counterparty_connection(),
+            ChannelError::missing_channel_on_destination(),
+            ChannelError::channel_proof(RelayerError::empty_response_proof()),
+            ChannelError::client_operation(
+                ClientId::default(),
+                ChainId::default(),
+                foreign_client_error(),
+            ),
+            ChannelError::fetch_signer(ChainId::default(), RelayerError::empty_response_proof()),
+            ChannelError::query(ChainId::default(), RelayerError::empty_response_proof()),
+            ChannelError::chain_query(ChainId::default(), RelayerError::empty_response_proof()),
+            ChannelError::query_channel(
+                ChannelId::default(),
+                SupervisorError::no_chains_available(),
+            ),
+            ChannelError::submit(ChainId::default(), RelayerError::empty_response_proof()),
+            ChannelError::handshake_finalize(),
+            ChannelError::partial_open_handshake(State::Init, State::TryOpen),
+            ChannelError::incomplete_channel_state(ChainId::default(), port_channel_id()),
+            ChannelError::channel_already_exist(ChannelId::default()),
+            ChannelError::mismatch_channel_ends(
+                ChainId::default(),
+                port_channel_id(),
+                port_channel_id(),
+                port_channel_id(),
+            ),
+            ChannelError::mismatch_port(
+                ChainId::default(),
+                PortId::transfer(),
+                ChainId::default(),
+                PortId::transfer(),
+                ChannelId::default(),
+            ),
+            ChannelError::missing_event("event".to_string()),
+            ChannelError::retry_internal("reason".to_string()),
+            ChannelError::tx_response(ChainId::default(), ChainError::default()),
+            ChannelError::invalid_event(IbcEvent::ChainError(ChainError::default())),
+            ChannelError::max_retry(
+                "description".to_string(),
+                1,
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                ChannelError::missing_event("event".to_string()),
+            ),
+            ChannelError::mismatch_ordering(
+                ChainId::default(),
+                ChannelId::default(),
+                Order::Ordered,
+                Order::Unordered,
+            ),
+            ChannelError::version_mismatch(
+                ChannelId::default(),
+                Version::ics20(),
+                Version::empty(),
+            ),
+            ChannelError::handshake_state_io(
+                "state.json".to_string(),
+                std::io::Error::from(std::io::ErrorKind::NotFound),
+            ),
+            ChannelError::handshake_state_encode("state.json".to_string(), json_error()),
+            ChannelError::handshake_state_decode("state.json".to_string(), json_error()),
+            ChannelError::incompatible_versions(Version::ics20(), Version::ics20_with_fee()),
+            ChannelError::incompatible_state(ChannelId::default(), State::Open, State::Closed),
+            ChannelError::missing_channel_id(IbcEvent::ChainError(ChainError::default())),
+            ChannelError::handshake_timed_out(
+                1,
+                progress(),
+                ChannelError::missing_event("event".to_string()),
+            ),
+            ChannelError::channel_not_open(ChainId::default(), ChannelId::default(), State::Closed),
+            ChannelError::mismatch_connection_hops(
+                ChainId::default(),
+                ChannelId::default(),
+                vec![ConnectionId::default()],
+                vec![],
+            ),
+            ChannelError::unexpected_event(
+                ChainId::default(),
+                "ChannelOpenInit".to_string(),
+                IbcEvent::ChainError(ChainError::default()),
+            ),
+            ChannelError::invalid_config("reason".to_string()),
+            ChannelError::subscription_closed(ChainId::default()),
+            ChannelError::connection_not_open(
+                ChainId::default(),
+                ConnectionId::default(),
+                ConnectionState::Init,
+            ),
+            ChannelError::client_expired_or_frozen(
+                ClientId::default(),
+                ChainId::default(),
+                foreign_client_error(),
+            ),
+            ChannelError::unknown_handshake_step("close".to_string()),
+        ];
+
+        let mut codes = errors.iter().map(|e| e.code()).collect::<Vec<_>>();
+
+        for code in &codes {
+            let number = code.strip_prefix("HERMES-CHAN-").expect("code prefix");
+            assert_eq!(number.len(), 3, "malformed code {code}");
+            assert!(number.parse::<u16>().is_ok(), "malformed code {code}");
+        }
+
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len(), "duplicate error codes");
+
+        // Every code up to the last one is in use, so that a variant missing from
+        // `errors` fails this test
+        let expected = (1..=codes.len())
+            .map(|number| format!("HERMES-CHAN-{number:03}"))
+            .collect::<Vec<_>>();
+        assert_eq!(codes, expected, "an error variant is missing from the test");
+
+        assert!(errors.iter().all(|e| !e.hint().is_empty()));
+    }
+}
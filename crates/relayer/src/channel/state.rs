@@ -0,0 +1,172 @@
+//! Persistence of the intermediate state of a channel handshake.
+//!
+//! The state is stored as a human-readable JSON file, so that a relayer
+//! which stopped half-way through a handshake can resume it from the
+//! channel ends which were already created, instead of starting over with
+//! a new `ChanOpenInit`. The file can also be edited for manual recovery.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics04_channel::channel::Order;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
+
+use crate::chain::handle::ChainHandle;
+use crate::channel::version::Version;
+use crate::channel::{Channel, ChannelError, ChannelSide};
+
+/// The persisted state of one side of a channel handshake.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandshakeSideState {
+    pub chain_id: ChainId,
+    pub connection_id: ConnectionId,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+}
+
+impl HandshakeSideState {
+    fn from_side<Chain: ChainHandle>(side: &ChannelSide<Chain>) -> Self {
+        Self {
+            chain_id: side.chain_id(),
+            connection_id: side.connection_id().clone(),
+            port_id: side.port_id().clone(),
+            channel_id: side.channel_id().cloned(),
+        }
+    }
+
+    fn matches<Chain: ChainHandle>(&self, side: &ChannelSide<Chain>) -> bool {
+        self.chain_id == side.chain_id()
+            && &self.connection_id == side.connection_id()
+            && &self.port_id == side.port_id()
+    }
+}
+
+/// The persisted state of a channel handshake.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandshakeState {
+    pub ordering: Order,
+    pub version: Option<Version>,
+    pub a_side: HandshakeSideState,
+    pub b_side: HandshakeSideState,
+}
+
+impl HandshakeState {
+    pub fn from_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
+        channel: &Channel<ChainA, ChainB>,
+    ) -> Self {
+        Self {
+            ordering: channel.ordering,
+            version: channel.src_version().cloned(),
+            a_side: HandshakeSideState::from_side(&channel.a_side),
+            b_side: HandshakeSideState::from_side(&channel.b_side),
+        }
+    }
+
+    /// Whether this state was stored for a handshake between the same
+    /// chains, connections and ports as the given channel.
+    pub fn matches<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        channel: &Channel<ChainA, ChainB>,
+    ) -> bool {
+        self.a_side.matches(&channel.a_side) && self.b_side.matches(&channel.b_side)
+    }
+
+    /// Loads the handshake state from the given file.
+    /// Returns `None` if the file does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>, ChannelError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let file = File::open(path)
+            .map_err(|e| ChannelError::handshake_state_io(path.display().to_string(), e))?;
+
+        let state = serde_json::from_reader(file)
+            .map_err(|e| ChannelError::handshake_state_decode(path.display().to_string(), e))?;
+
+        Ok(Some(state))
+    }
+
+    /// Stores the handshake state to the given file, overwriting any previous state.
+    ///
+    /// The state is written and synced to a temporary file in the same directory
+    /// first, which then replaces the previous state, so that a relayer stopping
+    /// mid-write, or a crash of the host, leaves the previous state intact.
+    pub fn store(&self, path: &Path) -> Result<(), ChannelError> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let io_error =
+            |path: &Path, e| ChannelError::handshake_state_io(path.display().to_string(), e);
+
+        let file = File::create(&tmp_path).map_err(|e| io_error(&tmp_path, e))?;
+
+        serde_json::to_writer_pretty(&file, self)
+            .map_err(|e| ChannelError::handshake_state_encode(path.display().to_string(), e))?;
+
+        file.sync_all().map_err(|e| io_error(&tmp_path, e))?;
+
+        fs::rename(&tmp_path, path).map_err(|e| io_error(path, e))?;
+
+        // Sync the directory as well for the rename to survive a crash of the host
+        #[cfg(unix)]
+        {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+
+            File::open(dir)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|e| io_error(dir, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handshake_state_roundtrip() {
+        let state = HandshakeState {
+            ordering: Order::Ordered,
+            version: Some(Version::ics20()),
+            a_side: HandshakeSideState {
+                chain_id: ChainId::from_string("chain-a"),
+                connection_id: ConnectionId::new(0),
+                port_id: PortId::transfer(),
+                channel_id: Some(ChannelId::new(3)),
+            },
+            b_side: HandshakeSideState {
+                chain_id: ChainId::from_string("chain-b"),
+                connection_id: ConnectionId::new(1),
+                port_id: PortId::transfer(),
+                channel_id: None,
+            },
+        };
+
+        let path = std::env::temp_dir().join(format!(
+            "hermes-handshake-state-{}.json",
+            std::process::id()
+        ));
+
+        assert_eq!(HandshakeState::load(&path).unwrap(), None);
+
+        state.store(&path).unwrap();
+        let loaded = HandshakeState::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Some(state));
+
+        // No temporary file is left behind
+        let mut tmp_path = path.into_os_string();
+        tmp_path.push(".tmp");
+        assert!(!Path::new(&tmp_path).exists());
+    }
+}
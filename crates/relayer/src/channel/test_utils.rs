@@ -0,0 +1,229 @@
+//! Chain handles and channels shared by the tests of the channel handshake
+//! and of the packet relaying.
+
+use core::str::FromStr;
+use core::time::Duration;
+
+use crossbeam_channel as channel;
+use ibc_proto::google::protobuf::Any;
+
+use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_ics07_header;
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, State as ConnectionState,
+};
+use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+use ibc_relayer_types::core::ics04_channel::packet::Packet;
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::mock::client_state::MockClientState;
+use ibc_relayer_types::mock::consensus_state::MockConsensusState;
+use ibc_relayer_types::mock::header::MockHeader;
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
+
+use crate::chain::endpoint::ChainStatus;
+use crate::chain::handle::{BaseChainHandle, ChainRequest};
+use crate::channel::version::Version;
+use crate::channel::{Channel, ChannelSide};
+use crate::client_state::AnyClientState;
+use crate::config::ChainConfig;
+use crate::consensus_state::AnyConsensusState;
+use crate::event::IbcEventWithHeight;
+use crate::light_client::AnyHeader;
+
+pub fn test_handle(chain_id: &str) -> BaseChainHandle {
+    // The receiving end is dropped straight away, so any request which
+    // reaches the chain runtime fails with an error instead of blocking.
+    let (sender, _) = channel::unbounded();
+    BaseChainHandle::new(ChainId::from_string(chain_id), sender)
+}
+
+/// The configuration of the first chain of the example configuration.
+pub fn test_chain_config() -> ChainConfig {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/config/fixtures/relayer_conf_example.toml"
+    );
+    let config = crate::config::load(path).expect("could not parse config");
+    config.chains[0].clone()
+}
+
+/// A chain handle whose requests are answered by `respond` on a separate thread.
+/// The requests which `respond` ignores fail as their reply channel is dropped.
+pub fn mock_handle(
+    chain_id: &str,
+    respond: impl Fn(ChainRequest) + Send + 'static,
+) -> BaseChainHandle {
+    let (sender, receiver) = channel::unbounded::<(tracing::Span, ChainRequest)>();
+    std::thread::spawn(move || {
+        for (_, request) in receiver {
+            respond(request);
+        }
+    });
+    BaseChainHandle::new(ChainId::from_string(chain_id), sender)
+}
+
+pub fn test_packet() -> Packet {
+    Packet {
+        sequence: 1.into(),
+        source_port: PortId::transfer(),
+        source_channel: ChannelId::new(0),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(1),
+        data: vec![],
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    }
+}
+
+pub fn test_channel(
+    a_channel_id: Option<ChannelId>,
+    b_channel_id: Option<ChannelId>,
+) -> Channel<BaseChainHandle, BaseChainHandle> {
+    Channel {
+        ordering: Order::Unordered,
+        a_side: ChannelSide::new(
+            test_handle("chain-a"),
+            ClientId::default(),
+            ConnectionId::default(),
+            PortId::transfer(),
+            a_channel_id,
+            None,
+        ),
+        b_side: ChannelSide::new(
+            test_handle("chain-b"),
+            ClientId::default(),
+            ConnectionId::default(),
+            PortId::transfer(),
+            b_channel_id,
+            None,
+        ),
+        connection_delay: Duration::ZERO,
+        overall_deadline: None,
+    }
+}
+
+pub fn channel_end(state: State, counterparty_channel_id: Option<ChannelId>) -> ChannelEnd {
+    ChannelEnd::new(
+        state,
+        Order::Unordered,
+        Counterparty::new(PortId::transfer(), counterparty_channel_id),
+        vec![ConnectionId::default()],
+        Version::empty(),
+    )
+}
+
+/// Responds to the queries of a chain whose channel ends are in the given state,
+/// channel 0 and channel 1 being each other's counterparty.
+pub fn ends_in_state(state: State) -> impl Fn(ChainRequest) + Send + 'static {
+    move |request: ChainRequest| match request {
+        ChainRequest::QueryApplicationStatus { reply_to } => {
+            let status = ChainStatus {
+                height: Height::new(0, 10).unwrap(),
+                timestamp: Timestamp::none(),
+            };
+            reply_to.send(Ok(status)).unwrap();
+        }
+        ChainRequest::QueryChannel {
+            request, reply_to, ..
+        } => {
+            let counterparty = if request.channel_id == ChannelId::new(0) {
+                ChannelId::new(1)
+            } else {
+                ChannelId::new(0)
+            };
+            let end = match state {
+                State::Uninitialized => ChannelEnd::default(),
+                state => channel_end(state, Some(counterparty)),
+            };
+            reply_to.send(Ok((end, None))).unwrap();
+        }
+        _ => {}
+    }
+}
+
+/// Responds to the requests which a [`ForeignClient`] makes to the chain hosting
+/// a mock client at `client_height`, when building an update of that client.
+pub fn respond_as_client_host(request: ChainRequest, client_height: Height) {
+    match request {
+        ChainRequest::QueryApplicationStatus { reply_to } => {
+            let status = ChainStatus {
+                height: Height::new(0, 100).unwrap(),
+                timestamp: Timestamp::none(),
+            };
+            reply_to.send(Ok(status)).unwrap();
+        }
+        ChainRequest::QueryClientState { reply_to, .. } => {
+            let client_state = MockClientState::new(MockHeader::new(client_height));
+            reply_to
+                .send(Ok((AnyClientState::Mock(client_state), None)))
+                .unwrap();
+        }
+        ChainRequest::QueryConsensusState {
+            request, reply_to, ..
+        } => {
+            let header = MockHeader::new(request.consensus_height);
+            let consensus_state = AnyConsensusState::Mock(MockConsensusState::new(header));
+            reply_to.send(Ok((consensus_state, None))).unwrap();
+        }
+        ChainRequest::Signer { reply_to } => {
+            let signer = Signer::from_str("cosmos1relayer").unwrap();
+            reply_to.send(Ok(signer)).unwrap();
+        }
+        _ => {}
+    }
+}
+
+/// Responds to the requests which a [`ForeignClient`] makes to the chain whose
+/// headers the client verifies, that chain being at `latest_height`.
+pub fn respond_as_client_source(request: ChainRequest, latest_height: Height) {
+    match request {
+        ChainRequest::QueryApplicationStatus { reply_to } => {
+            let status = ChainStatus {
+                height: latest_height,
+                timestamp: Timestamp::none(),
+            };
+            reply_to.send(Ok(status)).unwrap();
+        }
+        ChainRequest::BuildHeader { reply_to, .. } => {
+            let header = AnyHeader::Tendermint(get_dummy_ics07_header());
+            reply_to.send(Ok((header, vec![]))).unwrap();
+        }
+        _ => {}
+    }
+}
+
+/// Responds to the connection queries of a chain whose connection is in the given state.
+pub fn connection_with_state(state: ConnectionState) -> impl Fn(ChainRequest) + Send + 'static {
+    move |request: ChainRequest| {
+        if let ChainRequest::QueryConnection { reply_to, .. } = request {
+            let mut connection = ConnectionEnd::default();
+            connection.set_state(state);
+            reply_to.send(Ok((connection, None))).unwrap();
+        }
+    }
+}
+
+/// A chain handle which records the messages submitted to it, answering the
+/// submissions with `events` and the other requests with `respond`.
+pub fn recording_handle(
+    chain_id: &str,
+    events: Vec<IbcEventWithHeight>,
+    respond: impl Fn(ChainRequest) + Send + 'static,
+) -> (BaseChainHandle, channel::Receiver<Vec<Any>>) {
+    let (submitted, msgs) = channel::unbounded();
+    let handle = mock_handle(chain_id, move |request| match request {
+        ChainRequest::SendMessagesAndWaitCommit {
+            tracked_msgs,
+            reply_to,
+        } => {
+            submitted.send(tracked_msgs.msgs).unwrap();
+            reply_to.send(Ok(events.clone())).unwrap();
+        }
+        request => respond(request),
+    });
+    (handle, msgs)
+}
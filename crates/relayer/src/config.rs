@@ -29,6 +29,7 @@ use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
 use crate::error::Error as RelayerError;
 use crate::extension_options::ExtensionOptionDynamicFeeTx;
 use crate::keyring::Store;
+use crate::util::retry::{clamp_total, ExponentialBackoff};
 
 pub use crate::config::Error as ConfigError;
 pub use error::Error;
@@ -171,6 +172,14 @@ pub mod default {
         Duration::from_secs(30)
     }
 
+    pub fn handshake_timeout() -> Duration {
+        Duration::from_secs(10 * 60)
+    }
+
+    pub fn client_refresh_threshold() -> f64 {
+        1.0 / 3.0
+    }
+
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
@@ -420,6 +429,88 @@ impl Display for AddressType {
     }
 }
 
+/// Retry policy for the handshake steps that the relayer drives.
+///
+/// The delay between two attempts starts at `backoff` and grows by `multiplier`
+/// after every attempt, up to `max_delay`, with some random jitter added.
+///
+/// Unset fields fall back on defaults derived from the chain `max_block_time`.
+/// When no field is set on either chain, the connection handshake keeps its
+/// constant retry delay instead.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts for a single handshake step.
+    /// Default: as many attempts as fit in 10 blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+
+    /// Delay before the first retry. Default: a tenth of `max_block_time`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "humantime_serde"
+    )]
+    pub backoff: Option<Duration>,
+
+    /// Upper bound for the delay between two attempts. Default: `max_block_time`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "humantime_serde"
+    )]
+    pub max_delay: Option<Duration>,
+
+    /// Factor by which the delay grows after every attempt. Default: 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<f64>,
+}
+
+impl RetryPolicy {
+    /// Combines two policies, keeping the most lenient value of each field.
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            max_attempts: self.max_attempts.max(other.max_attempts),
+            backoff: self.backoff.max(other.backoff),
+            max_delay: self.max_delay.max(other.max_delay),
+            multiplier: match (self.multiplier, other.multiplier) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    /// The delays between the attempts of a handshake step, as an exponential
+    /// backoff with jitter. The fields left unset default to values derived
+    /// from the given maximum block time.
+    pub fn strategy(self, max_block_time: Duration) -> impl Iterator<Item = Duration> {
+        /// Approximate number of retries per block.
+        const PER_BLOCK_RETRIES: u32 = 10;
+
+        /// Maximum retry delay expressed in number of blocks
+        const BLOCK_NUMBER_DELAY: u32 = 10;
+
+        let base_delay = self.backoff.unwrap_or(max_block_time / PER_BLOCK_RETRIES);
+        let max_delay = self.max_delay.unwrap_or(max_block_time);
+
+        // Without an explicit number of attempts, retry for up to `BLOCK_NUMBER_DELAY` blocks.
+        // The whole handshake is bounded by `handshake_timeout` in any case.
+        let (max_attempts, max_elapsed) = match self.max_attempts {
+            // Every delay precedes a retry, after the first attempt
+            Some(max_attempts) => (max_attempts.saturating_sub(1) as usize, Duration::MAX),
+            None => (usize::MAX, max_block_time * BLOCK_NUMBER_DELAY),
+        };
+
+        clamp_total(
+            ExponentialBackoff::new(base_delay, max_delay)
+                .with_factor(self.multiplier.unwrap_or(2.0))
+                .take(max_attempts),
+            max_delay,
+            max_elapsed,
+        )
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChainConfig {
@@ -486,6 +577,27 @@ pub struct ChainConfig {
     #[serde(default)]
     pub sequential_batch_tx: bool,
 
+    /// When enabled, Hermes only performs queries against this chain and
+    /// refuses to submit any transaction to it.
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Maximum wall-clock time spent on a whole channel handshake before giving up,
+    /// unless the channel sets its own `overall_deadline`.
+    #[serde(default = "default::handshake_timeout", with = "humantime_serde")]
+    pub handshake_timeout: Duration,
+
+    /// The clients hosted on this chain are refreshed once the time left before
+    /// they expire drops below this fraction of their trusting period.
+    #[serde(default = "default::client_refresh_threshold")]
+    pub client_refresh_threshold: f64,
+
+    /// When enabled, the proofs of the channel handshake messages sent to this chain
+    /// are built at the latest height already trusted by its client of the sending
+    /// chain, when possible, rather than at the latest height of the sending chain.
+    #[serde(default)]
+    pub handshake_proofs_at_trusted_height: bool,
+
     // these two need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML
     /// The trust threshold defines what fraction of the total voting power of a known
     /// and trusted validator set is sufficient for a commit to be accepted going forward.
@@ -501,6 +613,9 @@ pub struct ChainConfig {
     pub address_type: AddressType,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
+
+    #[serde(default)]
+    pub handshake_retry: RetryPolicy,
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -536,8 +651,9 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
+    use core::time::Duration;
 
-    use super::{load, parse_gas_prices, store_writer};
+    use super::{load, parse_gas_prices, store_writer, RetryPolicy};
     use crate::config::GasPrice;
     use test_log::test;
 
@@ -566,6 +682,20 @@ mod tests {
         store_writer(&config, &mut buffer).unwrap();
     }
 
+    #[test]
+    fn handshake_timeout_defaults_to_ten_minutes() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        for chain in &config.chains {
+            assert_eq!(chain.handshake_timeout, Duration::from_secs(10 * 60));
+        }
+    }
+
     #[test]
     fn gas_price_from_str() {
         let gp_original = GasPrice::new(10.0, "atom".to_owned());
@@ -615,4 +745,40 @@ mod tests {
 
         assert_eq!(expected, parsed);
     }
+
+    #[test]
+    fn default_retry_strategy_spans_ten_blocks() {
+        let max_block_time = Duration::from_secs(10);
+
+        let delays: Vec<_> = RetryPolicy::default().strategy(max_block_time).collect();
+
+        assert!(delays.iter().all(|delay| *delay <= max_block_time));
+        assert_eq!(delays.iter().sum::<Duration>(), max_block_time * 10);
+
+        let policy = RetryPolicy {
+            max_attempts: Some(3),
+            ..Default::default()
+        };
+
+        // The first attempt is followed by two retries
+        assert_eq!(policy.strategy(max_block_time).count(), 2);
+    }
+
+    #[test]
+    fn retry_strategy_honors_multiplier() {
+        let policy = RetryPolicy {
+            max_attempts: Some(3),
+            backoff: Some(Duration::from_secs(1)),
+            max_delay: Some(Duration::from_secs(2)),
+            multiplier: Some(1.0),
+        };
+
+        let delays: Vec<_> = policy.strategy(Duration::from_secs(10)).collect();
+
+        // With a constant delay, the jitter is the only source of variation
+        assert_eq!(delays.len(), 2);
+        assert!(delays
+            .iter()
+            .all(|delay| *delay >= Duration::from_secs(1) && *delay < Duration::from_secs(2)));
+    }
 }
@@ -1,8 +1,10 @@
 use core::fmt::{Display, Error as FmtError, Formatter};
 use core::time::Duration;
 use std::thread;
+use std::time::Instant;
 
 use ibc_proto::google::protobuf::Any;
+use itertools::Either;
 use serde::Serialize;
 use tracing::{debug, error, info, warn};
 
@@ -25,10 +27,12 @@ use crate::chain::requests::{
     IncludeProof, PageRequest, QueryConnectionRequest, QueryConnectionsRequest, QueryHeight,
 };
 use crate::chain::tracking::TrackedMsgs;
+use crate::config::RetryPolicy;
 use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
+use crate::handshake::run_handshake_step;
 use crate::object::Connection as WorkerConnectionObject;
 use crate::util::pretty::{PrettyDuration, PrettyOption};
-use crate::util::retry::{retry_with_index, RetryResult};
+use crate::util::retry::RetryResult;
 use crate::util::task::Next;
 
 mod error;
@@ -38,8 +42,9 @@ pub use error::ConnectionError;
 pub const MAX_PACKET_DELAY: Duration = Duration::from_secs(120);
 
 mod handshake_retry {
-    //! Provides utility methods and constants to configure the retry behavior
-    //! for the connection handshake algorithm.
+    //! Provides utility methods to report the failures of the retried steps
+    //! of the connection handshake algorithm. The retry strategy itself is
+    //! configured per chain, see [`RetryPolicy`](crate::config::RetryPolicy).
 
     use crate::connection::ConnectionError;
     use crate::util::retry::{clamp_total, ConstantGrowth};
@@ -55,7 +60,8 @@ mod handshake_retry {
     /// Maximum retry delay expressed in number of blocks
     const BLOCK_NUMBER_DELAY: u32 = 10;
 
-    /// The default retry strategy.
+    /// The default retry strategy, used when neither chain configures a
+    /// `handshake_retry` policy.
     /// We retry with a constant backoff strategy. The strategy is parametrized by the
     /// maximum block time expressed as a `Duration`.
     pub fn default_strategy(max_block_times: Duration) -> impl Iterator<Item = Duration> {
@@ -73,8 +79,9 @@ mod handshake_retry {
     pub fn from_retry_error(
         e: retry::Error<ConnectionError>,
         description: String,
+        elapsed: Duration,
     ) -> ConnectionError {
-        ConnectionError::max_retry(description, e.tries, e.total_delay, e.error)
+        ConnectionError::max_retry(description, e.tries, e.total_delay, elapsed, e.error)
     }
 }
 
@@ -157,7 +164,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
         a_to_b_client: ForeignClient<ChainB, ChainA>,
         delay_period: Duration,
     ) -> Result<Self, ConnectionError> {
-        Self::validate_clients(&b_to_a_client, &a_to_b_client)?;
+        Self::validate(&b_to_a_client, &a_to_b_client)?;
 
         // Validate the delay period against the upper bound
         if delay_period > MAX_PACKET_DELAY {
@@ -288,7 +295,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
         b_client: ForeignClient<ChainB, ChainA>,
         conn_end_a: &IdentifiedConnectionEnd,
     ) -> Result<Connection<ChainA, ChainB>, ConnectionError> {
-        Self::validate_clients(&a_client, &b_client)?;
+        Self::validate(&a_client, &b_client)?;
 
         // Validate the connection end
         if conn_end_a.end().client_id().ne(a_client.id()) {
@@ -337,10 +344,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
     }
 
     // Verifies that the two clients are mutually consistent, i.e., they serve the same two chains.
-    fn validate_clients(
+    /// Checks that the two clients form a valid pair for a connection, before any
+    /// query is made to the chains: the two chains must be distinct, and the
+    /// source chain of each client must be the destination chain of the other.
+    pub fn validate(
         a_client: &ForeignClient<ChainA, ChainB>,
         b_client: &ForeignClient<ChainB, ChainA>,
     ) -> Result<(), ConnectionError> {
+        if a_client.src_chain().id() == a_client.dst_chain().id() {
+            return Err(ConnectionError::same_chain(a_client.src_chain().id()));
+        }
+
         if a_client.src_chain().id() != b_client.dst_chain().id() {
             return Err(ConnectionError::chain_id_mismatch(
                 a_client.src_chain().id(),
@@ -437,21 +451,26 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
         }
     }
 
-    /// Returns a `Duration` representing the maximum value among the
-    /// [`ChainConfig.max_block_time`] for the two networks that
-    /// this connection belongs to.
-    fn max_block_times(&self) -> Result<Duration, ConnectionError> {
-        let a_block_time = self
-            .a_chain()
-            .config()
-            .map_err(ConnectionError::relayer)?
-            .max_block_time;
-        let b_block_time = self
-            .b_chain()
-            .config()
-            .map_err(ConnectionError::relayer)?
-            .max_block_time;
-        Ok(a_block_time.max(b_block_time))
+    /// Returns the retry strategy for the handshake steps, combining the
+    /// [`ChainConfig.max_block_time`] and [`ChainConfig.handshake_retry`]
+    /// of the two networks that this connection belongs to.
+    ///
+    /// Unless one of the chains configures a policy, the connection handshake
+    /// keeps retrying at a constant pace, see [`handshake_retry::default_strategy`].
+    fn handshake_retry_strategy(&self) -> Result<impl Iterator<Item = Duration>, ConnectionError> {
+        let a_config = self.a_chain().config().map_err(ConnectionError::relayer)?;
+        let b_config = self.b_chain().config().map_err(ConnectionError::relayer)?;
+
+        let max_block_times = a_config.max_block_time.max(b_config.max_block_time);
+        let policy = a_config.handshake_retry.max(b_config.handshake_retry);
+
+        if policy == RetryPolicy::default() {
+            Ok(Either::Left(handshake_retry::default_strategy(
+                max_block_times,
+            )))
+        } else {
+            Ok(Either::Right(policy.strategy(max_block_times)))
+        }
     }
 
     pub fn flipped(&self) -> Connection<ChainB, ChainA> {
@@ -656,29 +675,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
 
     /// Executes the connection handshake protocol (ICS003)
     fn handshake(&mut self) -> Result<(), ConnectionError> {
-        let max_block_times = self.max_block_times()?;
-
-        retry_with_index(handshake_retry::default_strategy(max_block_times), |_| {
-            if let Err(e) = self.do_conn_open_handshake() {
-                if e.is_expired_or_frozen_error() {
-                    RetryResult::Err(e)
-                } else {
-                    RetryResult::Retry(e)
-                }
-            } else {
-                RetryResult::Ok(())
-            }
-        })
-        .map_err(|err| {
-            error!("failed to open connection after {} retries", err.tries);
-
-            handshake_retry::from_retry_error(
-                err,
-                format!("failed to finish connection handshake for {:?}", self),
-            )
-        })?;
-
-        Ok(())
+        let delays: Vec<Duration> = self.handshake_retry_strategy()?.collect();
+        let start = Instant::now();
+
+        run_handshake_step(&delays, "open connection", || self.do_conn_open_handshake()).map_err(
+            |err| {
+                handshake_retry::from_retry_error(
+                    err,
+                    format!("failed to finish connection handshake for {:?}", self),
+                    start.elapsed(),
+                )
+            },
+        )
     }
 
     pub fn counterparty_state(&self) -> Result<State, ConnectionError> {
@@ -920,7 +928,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
                 info!("🥂 {} => {}", self.dst_chain().id(), result);
                 Ok(result.event)
             }
-            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.clone())),
+            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.to_string())),
             _ => Err(ConnectionError::invalid_event(result.event)),
         }
     }
@@ -1092,7 +1100,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
                 info!("🥂 {} => {}", self.dst_chain().id(), result);
                 Ok(result.event)
             }
-            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.clone())),
+            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.to_string())),
             _ => Err(ConnectionError::invalid_event(result.event)),
         }
     }
@@ -1207,7 +1215,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
                 info!("🥂 {} => {}", self.dst_chain().id(), result);
                 Ok(result.event)
             }
-            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.clone())),
+            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.to_string())),
             _ => Err(ConnectionError::invalid_event(result.event)),
         }
     }
@@ -1295,7 +1303,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
                 info!("🥂 {} => {}", self.dst_chain().id(), result);
                 Ok(result.event)
             }
-            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.clone())),
+            IbcEvent::ChainError(e) => Err(ConnectionError::tx_response(e.to_string())),
             _ => Err(ConnectionError::invalid_event(result.event)),
         }
     }
@@ -1380,3 +1388,19 @@ fn check_destination_connection_state(
         Err(ConnectionError::connection_already_exists(connection_id))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::handshake_retry::default_strategy;
+
+    #[test]
+    fn default_strategy_retries_at_a_constant_pace_for_ten_blocks() {
+        let max_block_time = Duration::from_secs(10);
+        let delays: Vec<Duration> = default_strategy(max_block_time).collect();
+
+        assert_eq!(delays.len(), 100);
+        assert!(delays.iter().all(|d| *d == Duration::from_secs(1)));
+    }
+}
@@ -8,6 +8,7 @@ use ibc_relayer_types::events::IbcEvent;
 
 use crate::error::Error as RelayerError;
 use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
+use crate::handshake::HandshakeStepError;
 use crate::supervisor::Error as SupervisorError;
 
 define_error! {
@@ -116,6 +117,13 @@ define_error! {
                     e.source_chain_id, e.destination_chain_id)
             },
 
+        SameChain
+            { chain_id: ChainId }
+            |e| {
+                format!("the two ends of the connection are on the same chain ({})",
+                    e.chain_id)
+            },
+
         ConnectionNotOpen
             {
                 state: State,
@@ -176,11 +184,12 @@ define_error! {
                 description: String,
                 tries: u64,
                 total_delay: Duration,
+                elapsed: Duration,
             }
             [ Self ]
             | e | {
-                format_args!("error after maximum retry of {} and total delay of {}s: {}",
-                    e.tries, e.total_delay.as_secs(), e.description)
+                format_args!("error after {} attempts in {}s (total delay of {}s): {}",
+                    e.tries, e.elapsed.as_secs(), e.total_delay.as_secs(), e.description)
             },
 
     }
@@ -200,3 +209,9 @@ impl HasExpiredOrFrozenError for ConnectionError {
         self.detail().is_expired_or_frozen_error()
     }
 }
+
+impl HandshakeStepError for ConnectionError {
+    fn should_retry(&self) -> bool {
+        !self.is_expired_or_frozen_error()
+    }
+}
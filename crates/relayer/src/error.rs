@@ -564,6 +564,12 @@ define_error! {
             { key_type: KeyType }
             |e| {
                 format!("Invalid key type {} for the current chain", e.key_type)
+            },
+
+        ReadOnlyMode
+            { chain_id: ChainId }
+            |e| {
+                format_args!("chain '{}' is configured in read-only mode, refusing to submit transactions", e.chain_id)
             }
     }
 }
@@ -573,6 +579,10 @@ impl Error {
         Error::channel_send()
     }
 
+    pub fn is_read_only_mode_error(&self) -> bool {
+        self.detail().is_read_only_mode_error()
+    }
+
     pub fn is_trusted_state_outside_trusting_period_error(&self) -> bool {
         match self.detail() {
             ErrorDetail::LightClientVerification(e) => matches!(
@@ -584,6 +594,12 @@ impl Error {
     }
 }
 
+impl ErrorDetail {
+    pub fn is_read_only_mode_error(&self) -> bool {
+        matches!(self, Self::ReadOnlyMode(_))
+    }
+}
+
 impl GrpcStatusSubdetail {
     /// Check whether this gRPC error matches
     /// - message: verification failed: ... failed packet acknowledgement verification for client: client state height < proof height ...
@@ -301,6 +301,25 @@ impl HasExpiredOrFrozenError for ForeignClientError {
     }
 }
 
+impl ForeignClientErrorDetail {
+    /// Whether the client operation failed because the host chain is in
+    /// read-only mode and refused to submit the transaction.
+    pub fn is_read_only_mode_error(&self) -> bool {
+        match self {
+            Self::ClientCreate(e) => e.source.is_read_only_mode_error(),
+            Self::ClientUpdate(e) => e.source.is_read_only_mode_error(),
+            Self::ClientRefresh(e) => e.source.is_read_only_mode_error(),
+            _ => false,
+        }
+    }
+}
+
+impl ForeignClientError {
+    pub fn is_read_only_mode_error(&self) -> bool {
+        self.detail().is_read_only_mode_error()
+    }
+}
+
 /// User-supplied options for the [`ForeignClient::build_create_client`] operation.
 ///
 /// Currently, the parameters are specific to the Tendermint-based chains.
@@ -652,6 +671,20 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         Ok(res[0].clone())
     }
 
+    /// Creates the client on the destination chain, from the state of the source
+    /// chain at its latest height, and returns the identifier of the new client.
+    pub fn create_client_and_send(
+        &self,
+        options: CreateOptions,
+    ) -> Result<ClientId, ForeignClientError> {
+        let event_with_height = self.build_create_client_and_send(options)?;
+
+        let client_id = extract_client_id(&event_with_height.event)?.clone();
+        debug!(id = %client_id, ?event_with_height.event, "event emitted after creation");
+
+        Ok(client_id)
+    }
+
     /// Sends the client creation transaction & subsequently sets the id of this ForeignClient
     #[instrument(
         name = "foreign_client.create",
@@ -660,17 +693,14 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         fields(client = %self)
     )]
     fn create(&mut self) -> Result<(), ForeignClientError> {
-        let event_with_height = self
-            .build_create_client_and_send(CreateOptions::default())
+        self.id = self
+            .create_client_and_send(CreateOptions::default())
             .map_err(|e| {
                 error!("failed to create client: {}", e);
                 e
             })?;
 
-        self.id = extract_client_id(&event_with_height.event)?.clone();
-
         info!(id = %self.id, "🍭 client was created successfully");
-        debug!(id = %self.id, ?event_with_height.event, "event emitted after creation");
 
         Ok(())
     }
@@ -833,22 +863,42 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
     fn try_refresh(&mut self) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
         let (client_state, elapsed) = self.validated_client_state()?;
 
-        // The refresh_window is the maximum duration
-        // we can backoff between subsequent client updates.
-        let refresh_window = client_state.refresh_period();
+        let threshold = self
+            .dst_chain
+            .config()
+            .map_err(|e| {
+                ForeignClientError::client_refresh(
+                    self.id().clone(),
+                    "failed querying the config of the dst chain".to_string(),
+                    e,
+                )
+            })?
+            .client_refresh_threshold;
+
+        if let (Some(elapsed), Some(trusting_period)) = (elapsed, client_state.trusting_period()) {
+            telemetry!(
+                client_expiry,
+                &self.src_chain.id(),
+                &self.dst_chain.id(),
+                &self.id,
+                trusting_period.saturating_sub(elapsed).as_secs()
+            );
+        }
 
-        match (elapsed, refresh_window) {
-            (None, _) | (_, None) => Ok(None),
-            (Some(elapsed), Some(refresh_window)) => {
-                if elapsed > refresh_window {
-                    info!(?elapsed, ?refresh_window, "client needs to be refreshed");
+        match (elapsed, client_state.trusting_period()) {
+            (Some(elapsed), Some(trusting_period))
+                if needs_refresh(elapsed, trusting_period, threshold) =>
+            {
+                warn!(
+                    remaining = ?trusting_period.saturating_sub(elapsed),
+                    ?trusting_period,
+                    "client is close to expiry and needs to be refreshed"
+                );
 
-                    self.build_latest_update_client_and_send()
-                        .map_or_else(Err, |ev| Ok(Some(ev)))
-                } else {
-                    Ok(None)
-                }
+                self.build_latest_update_client_and_send()
+                    .map_or_else(Err, |ev| Ok(Some(ev)))
             }
+            _ => Ok(None),
         }
     }
 
@@ -1762,3 +1812,45 @@ pub fn extract_client_id(event: &IbcEvent) -> Result<&ClientId, ForeignClientErr
         )),
     }
 }
+
+/// Whether a client updated `elapsed` ago must be refreshed, ie. whether the time
+/// left before its trusting period elapses is below `threshold` of that period.
+fn needs_refresh(elapsed: Duration, trusting_period: Duration, threshold: f64) -> bool {
+    let remaining = trusting_period.saturating_sub(elapsed);
+    remaining < trusting_period.mul_f64(threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clients_are_refreshed_close_to_expiry() {
+        let trusting_period = Duration::from_secs(3 * 3600);
+
+        assert!(!needs_refresh(
+            Duration::from_secs(3600),
+            trusting_period,
+            1.0 / 3.0
+        ));
+        assert!(needs_refresh(
+            Duration::from_secs(2 * 3600 + 1),
+            trusting_period,
+            1.0 / 3.0
+        ));
+
+        // A higher threshold refreshes the client earlier
+        assert!(needs_refresh(
+            Duration::from_secs(3600 + 1),
+            trusting_period,
+            2.0 / 3.0
+        ));
+
+        // The time left saturates at zero once the trusting period has elapsed
+        assert!(needs_refresh(
+            Duration::from_secs(4 * 3600),
+            trusting_period,
+            1.0 / 3.0
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+//! Retry machinery shared by the connection and channel handshakes.
+
+use core::fmt::Display;
+use core::time::Duration;
+use std::time::Instant;
+
+use tracing::{error, warn};
+
+use crate::util::pretty::PrettyDuration;
+use crate::util::retry::{retry_with_index, RetryError, RetryResult};
+
+/// The errors of a handshake step, which tell whether performing the step
+/// again may succeed.
+pub trait HandshakeStepError: Display {
+    /// Whether the step which failed with this error is worth retrying. Steps
+    /// failing because of an expired or frozen client, or because the chain
+    /// rejected the transaction for good, are not.
+    fn should_retry(&self) -> bool;
+}
+
+/// Performs the handshake step `step_name` until it succeeds, waiting for the
+/// given delays between the attempts, and stops at the first error which is not
+/// worth retrying.
+///
+/// On failure, returns the last error along with the number of attempts and the
+/// total delay waited for, and logs them with the step name.
+pub fn run_handshake_step<E: HandshakeStepError>(
+    delays: &[Duration],
+    step_name: &str,
+    mut step: impl FnMut() -> Result<(), E>,
+) -> Result<(), RetryError<E>> {
+    let start = Instant::now();
+
+    retry_with_index(delays.iter().copied(), |index| match step() {
+        Ok(()) => RetryResult::Ok(()),
+        Err(e) if !e.should_retry() => RetryResult::Err(e),
+        Err(e) => {
+            // `index` is the number of the attempt which just failed, starting at 1
+            if let Some(delay) = delays.get(index.saturating_sub(1) as usize) {
+                warn!(
+                    "failed to {} at attempt {}, retrying in {}: {}",
+                    step_name,
+                    index,
+                    PrettyDuration(delay),
+                    e
+                );
+            }
+
+            RetryResult::Retry(e)
+        }
+    })
+    .map_err(|e| {
+        error!(
+            "failed to {} after {} attempts in {}",
+            step_name,
+            e.tries,
+            PrettyDuration(&start.elapsed())
+        );
+
+        e
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::fmt::{Formatter, Result as FmtResult};
+
+    struct StepError {
+        retryable: bool,
+    }
+
+    impl Display for StepError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            write!(f, "step error (retryable: {})", self.retryable)
+        }
+    }
+
+    impl HandshakeStepError for StepError {
+        fn should_retry(&self) -> bool {
+            self.retryable
+        }
+    }
+
+    #[test]
+    fn steps_are_retried_until_they_succeed() {
+        let mut attempts = 0;
+
+        let result = run_handshake_step(&[Duration::ZERO; 5], "test step", || {
+            attempts += 1;
+            if attempts < 3 {
+                Err(StepError { retryable: true })
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn the_last_error_is_returned_with_the_attempt_count() {
+        let e = run_handshake_step(&[Duration::ZERO; 2], "test step", || {
+            Err(StepError { retryable: true })
+        })
+        .expect_err("expected the retries to be exhausted");
+
+        assert_eq!(e.tries, 3);
+        assert!(e.error.retryable);
+    }
+
+    #[test]
+    fn steps_are_not_retried_after_a_permanent_error() {
+        let mut attempts = 0;
+
+        let e = run_handshake_step(&[Duration::ZERO; 5], "test step", || {
+            attempts += 1;
+            Err(StepError { retryable: false })
+        })
+        .expect_err("expected a permanent failure");
+
+        assert_eq!(attempts, 1);
+        assert_eq!(e.tries, 1);
+    }
+}
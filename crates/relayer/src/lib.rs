@@ -33,6 +33,7 @@ pub mod error;
 pub mod event;
 pub mod extension_options;
 pub mod foreign_client;
+pub mod handshake;
 pub mod keyring;
 pub mod light_client;
 pub mod link;
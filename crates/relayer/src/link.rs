@@ -152,6 +152,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
                 None,
             ),
             connection_delay: a_connection.delay_period(),
+            overall_deadline: None,
         };
 
         if auto_register_counterparty_payee && a_channel.version.supports_fee() {
@@ -5,7 +5,7 @@ use std::time::Instant;
 use tracing::{debug, error, trace, trace_span};
 
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ChainError, IbcEvent};
 
 use crate::chain::requests::{QueryTxHash, QueryTxRequest};
 use crate::chain::tracking::TrackingId;
@@ -103,13 +103,14 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                     response
                 );
 
-                let error_event = IbcEvent::ChainError(format!(
-                    "deliver_tx on chain {} for Tx hash {} reports error: code={:?}, log={:?}",
-                    self.chain_id(),
-                    response.hash,
-                    response.code,
-                    response.log
-                ));
+                let error_event = IbcEvent::ChainError(ChainError {
+                    origin: format!("deliver_tx on chain {}", self.chain_id()),
+                    tx_hash: response.hash.to_string(),
+                    code: response.code.value(),
+                    // `broadcast_tx_sync` responses do not report the codespace of the error
+                    codespace: String::new(),
+                    log: response.log.clone(),
+                });
                 error_events.push(error_event);
             } else {
                 tx_hashes.push(response.hash);
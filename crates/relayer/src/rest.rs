@@ -4,10 +4,11 @@ use tracing::{error, trace};
 use crate::{
     config::Config,
     rest::request::ReplySender,
-    rest::request::{Request, VersionInfo},
+    rest::request::{HealthReport, Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
 };
 
+pub mod health;
 pub mod request;
 
 mod error;
@@ -31,6 +32,7 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 //  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    Health(ReplySender<HealthReport>),
 }
 
 /// Process incoming REST requests.
@@ -82,6 +84,12 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::Health { reply_to } => {
+                trace!("Health");
+
+                return Some(Command::Health(reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
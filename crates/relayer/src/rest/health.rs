@@ -0,0 +1,175 @@
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crossbeam_channel as channel;
+use tracing::debug;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::config::ChainConfig;
+use crate::rest::request::{ChainHealth, HealthReport, HealthStatus};
+
+/// How long the chains have to answer a health check. The chains which have not
+/// answered by then are reported as not connected.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The latest height of a chain, or `None` if it could not be queried.
+type ProbeResult = (ChainId, Option<Height>);
+
+/// The queries of the latest height of the chains which did not answer yet, shared
+/// by the health checks so that each chain is only queried once at a time.
+///
+/// A health check of a chain which is still being queried waits for the result of
+/// the query in flight instead of sending another one, so that a chain which does
+/// not answer does not accumulate queries, nor the threads waiting on them.
+#[derive(Clone, Debug, Default)]
+pub struct HealthProbes {
+    in_flight: Arc<Mutex<BTreeMap<ChainId, Vec<channel::Sender<ProbeResult>>>>>,
+}
+
+impl HealthProbes {
+    /// Sends the latest height of the chain to `reply_to` once it is known, querying
+    /// it unless a query of the chain is already in flight.
+    fn probe<Chain: ChainHandle>(&self, handle: Chain, reply_to: channel::Sender<ProbeResult>) {
+        let chain_id = handle.id();
+
+        match self.in_flight.lock().unwrap().entry(chain_id.clone()) {
+            Entry::Occupied(mut waiting) => waiting.get_mut().push(reply_to),
+            Entry::Vacant(waiting) => {
+                waiting.insert(vec![reply_to]);
+
+                let in_flight = self.in_flight.clone();
+
+                // The query may outlive the checks, its result is then ignored
+                std::thread::spawn(move || {
+                    let height = handle
+                        .query_latest_height()
+                        .map_err(|e| debug!("health check of chain {} failed: {}", chain_id, e))
+                        .ok();
+
+                    let waiting = in_flight.lock().unwrap().remove(&chain_id);
+                    for reply_to in waiting.into_iter().flatten() {
+                        let _ = reply_to.send((chain_id.clone(), height));
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Checks the connectivity of the given chains by querying their latest height.
+///
+/// The chains are queried concurrently and the check returns within `timeout`,
+/// regardless of chains which do not answer. A chain whose query from a previous
+/// check is still in flight is not queried again, see [`HealthProbes`]. The chains
+/// without a handle, eg. because their runtime failed to spawn, are reported as
+/// not connected. The report also tells which chains are configured in read-only mode.
+pub fn check_health<Chain: ChainHandle>(
+    probes: &HealthProbes,
+    chains: Vec<(ChainConfig, Option<Chain>)>,
+    timeout: Duration,
+) -> HealthReport {
+    let deadline = Instant::now() + timeout;
+    let (sender, receiver) = channel::unbounded();
+
+    let mut report: BTreeMap<ChainId, ChainHealth> = BTreeMap::new();
+
+    for (chain_config, handle) in chains {
+        report.insert(
+            chain_config.id.clone(),
+            ChainHealth::disconnected(chain_config.read_only),
+        );
+
+        if let Some(handle) = handle {
+            probes.probe(handle, sender.clone());
+        }
+    }
+
+    drop(sender);
+
+    while let Ok((chain_id, height)) = receiver.recv_deadline(deadline) {
+        if let (Some(height), Some(chain)) = (height, report.get_mut(&chain_id)) {
+            chain.connected = true;
+            chain.latest_height = Some(height.revision_height());
+        }
+    }
+
+    let status = if report.values().all(|chain| chain.connected) {
+        HealthStatus::Ok
+    } else {
+        HealthStatus::Degraded
+    };
+
+    HealthReport {
+        status,
+        chains: report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::timestamp::Timestamp;
+
+    use crate::chain::endpoint::ChainStatus;
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+
+    #[test]
+    fn chains_are_only_queried_once_at_a_time() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = crate::config::load(path).expect("could not parse config");
+        let chain_config = config.chains[0].clone();
+
+        // A chain whose requests are only answered when the test says so
+        let (sender, requests) = channel::unbounded();
+        let handle = BaseChainHandle::new(chain_config.id.clone(), sender);
+        let chains = vec![(chain_config.clone(), Some(handle))];
+
+        let probes = HealthProbes::default();
+
+        for _ in 0..2 {
+            let report = check_health(&probes, chains.clone(), Duration::from_millis(50));
+            assert_eq!(report.status, HealthStatus::Degraded);
+            assert!(!report.chains[&chain_config.id].connected);
+        }
+
+        // The chain did not answer, so the second check did not query it again
+        assert_eq!(requests.len(), 1);
+
+        let check = {
+            let probes = probes.clone();
+            std::thread::spawn(move || check_health(&probes, chains, Duration::from_secs(10)))
+        };
+
+        // Wait for the third check to join the query in flight
+        while probes.in_flight.lock().unwrap()[&chain_config.id].len() < 3 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        match requests.recv().unwrap() {
+            (_, ChainRequest::QueryApplicationStatus { reply_to }) => {
+                let status = ChainStatus {
+                    height: Height::new(0, 10).unwrap(),
+                    timestamp: Timestamp::none(),
+                };
+                reply_to.send(Ok(status)).unwrap();
+            }
+            (_, request) => panic!("unexpected request: {request:?}"),
+        }
+
+        let report = check.join().unwrap();
+        assert_eq!(report.status, HealthStatus::Ok);
+        assert_eq!(report.chains[&chain_config.id].latest_height, Some(10));
+        assert!(requests.is_empty());
+    }
+}
@@ -1,4 +1,6 @@
-use serde::Serialize;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
@@ -17,6 +19,42 @@ pub struct VersionInfo {
     pub version: String,
 }
 
+/// Whether all the chains of the relayer are reachable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+}
+
+/// The connectivity of a chain, as observed by a health check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHealth {
+    pub connected: bool,
+    pub latest_height: Option<u64>,
+    /// Whether the chain is configured in read-only mode, ie. the relayer never
+    /// submits transactions to it.
+    pub read_only: bool,
+}
+
+impl ChainHealth {
+    pub fn disconnected(read_only: bool) -> Self {
+        Self {
+            connected: false,
+            latest_height: None,
+            read_only,
+        }
+    }
+}
+
+/// The outcome of a health check of the relayer: the status is `degraded` as soon
+/// as one of the chains is not connected.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub chains: BTreeMap<ChainId, ChainHealth>,
+}
+
 /// REST API request variants
 #[derive(Clone, Debug)]
 pub enum Request {
@@ -36,4 +74,8 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    Health {
+        reply_to: ReplySender<HealthReport>,
+    },
 }
@@ -182,6 +182,25 @@ pub fn sdk_error_from_tx_result(code: Code, codespace: &str) -> SdkError {
     }
 }
 
+/// Whether a transaction which failed with the given code in the given codespace
+/// can never succeed when submitted again as is, e.g. because the targeted port
+/// does not exist. Running out of gas or paying too low a fee are not permanent,
+/// as the gas estimate and the gas price may differ on the next attempt.
+///
+/// Codes are only meaningful within their codespace, so the errors of
+/// `broadcast_tx_sync` (ie. `CheckTx`) responses, which do not report one,
+/// are never classified as permanent.
+/// Cf: <https://github.com/cosmos/cosmos-sdk/blob/v0.42.10/types/errors/errors.go>
+pub fn is_permanent_tx_error(code: u32, codespace: &str) -> bool {
+    match codespace {
+        // tx parse error, unauthorized, insufficient funds
+        "sdk" => matches!(code, 2 | 4 | 5),
+        // The port is not bound or its module does not route the message
+        "port" => true,
+        _ => false,
+    }
+}
+
 /// Converts error codes originating from `broadcast_tx_sync` responses
 /// into IBC relayer domain-type errors.
 /// See [`tendermint_rpc::endpoint::broadcast::tx_sync::Response`].
@@ -24,7 +24,10 @@ use crate::{
     },
     object::Object,
     registry::{Registry, SharedRegistry},
-    rest,
+    rest::{
+        self,
+        health::{check_health, HealthProbes, HEALTH_CHECK_TIMEOUT},
+    },
     supervisor::scan::ScanMode,
     telemetry,
     util::{
@@ -255,11 +258,19 @@ pub fn spawn_rest_worker<Chain: ChainHandle>(
     workers: Arc<RwLock<WorkerMap>>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
+    let probes = HealthProbes::default();
+
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(
+                &config,
+                &registry.read(),
+                &workers.acquire_read(),
+                &probes,
+                &rest_rx,
+            );
 
             Ok(Next::Continue)
         },
@@ -638,17 +649,20 @@ fn handle_rest_requests<Chain: ChainHandle>(
     config: &Config,
     registry: &Registry<Chain>,
     workers: &WorkerMap,
+    probes: &HealthProbes,
     rest_rx: &rest::Receiver,
 ) {
     if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+        handle_rest_cmd(config, registry, workers, probes, cmd);
     }
 }
 
 #[instrument(name = "supervisor.handle_rest_cmd", level = "error", skip_all)]
 fn handle_rest_cmd<Chain: ChainHandle>(
+    config: &Config,
     registry: &Registry<Chain>,
     workers: &WorkerMap,
+    probes: &HealthProbes,
     m: rest::Command,
 ) {
     match m {
@@ -658,6 +672,26 @@ fn handle_rest_cmd<Chain: ChainHandle>(
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+
+        rest::Command::Health(reply) => {
+            let chains = config
+                .chains
+                .iter()
+                .map(|chain_config| {
+                    let handle = registry.chains().find(|c| c.id() == chain_config.id);
+                    (chain_config.clone(), handle.cloned())
+                })
+                .collect();
+
+            // The chains are queried on a separate thread so as not to hold up the supervisor
+            let probes = probes.clone();
+            std::thread::spawn(move || {
+                let report = check_health(&probes, chains, HEALTH_CHECK_TIMEOUT);
+                reply
+                    .send(Ok(report))
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            });
+        }
     }
 }
 
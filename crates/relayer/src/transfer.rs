@@ -209,7 +209,7 @@ pub fn send_messages<Chain: ChainHandle>(
         None => Ok(events_with_heights),
         Some(err) => {
             if let IbcEvent::ChainError(ref err) = err.event {
-                Err(TransferError::tx_response(err.clone()))
+                Err(TransferError::tx_response(err.to_string()))
             } else {
                 panic!(
                     "internal error, expected IBCEvent::ChainError, got {:?}",
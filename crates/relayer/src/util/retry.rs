@@ -41,6 +41,87 @@ impl Iterator for ConstantGrowth {
     }
 }
 
+/// An exponential backoff strategy, where the delay doubles (or grows by the
+/// configured factor) after every attempt, starting from `base` and never
+/// exceeding `max_delay`.
+///
+/// A random jitter of up to `base` is added to every delay, so that concurrent
+/// relayers retrying against the same chain do not do so in lockstep.
+#[derive(Copy, Clone, Debug)]
+pub struct ExponentialBackoff {
+    base: Duration,
+    max_delay: Duration,
+    factor: f64,
+    attempt: u32,
+    jitter: bool,
+}
+
+impl ExponentialBackoff {
+    pub const fn new(base: Duration, max_delay: Duration) -> Self {
+        Self {
+            base,
+            max_delay,
+            factor: 2.0,
+            attempt: 0,
+            jitter: true,
+        }
+    }
+
+    /// Sets the factor by which the delay grows after every attempt.
+    /// Factors below 1 are treated as 1, ie. a constant delay.
+    pub fn with_factor(mut self, factor: f64) -> Self {
+        self.factor = factor.max(1.0);
+        self
+    }
+
+    /// Disables the random jitter, making the delays deterministic.
+    pub const fn without_jitter(mut self) -> Self {
+        self.jitter = false;
+        self
+    }
+}
+
+impl Iterator for ExponentialBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        // Compute in nanoseconds so that integral factors yield exact delays
+        let nanos = self.base.as_nanos() as f64 * self.factor.powi(self.attempt as i32);
+        let delay = if nanos < self.max_delay.as_nanos() as f64 {
+            Duration::from_nanos(nanos.round() as u64)
+        } else {
+            self.max_delay
+        };
+
+        let jitter = if self.jitter {
+            random_jitter(self.base)
+        } else {
+            Duration::ZERO
+        };
+
+        self.attempt = self.attempt.saturating_add(1);
+
+        Some(delay.saturating_add(jitter).min(self.max_delay))
+    }
+}
+
+/// Returns a random duration in the range `[0, max)`.
+fn random_jitter(max: Duration) -> Duration {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let max_nanos = max.as_nanos() as u64;
+    if max_nanos == 0 {
+        return Duration::ZERO;
+    }
+
+    // Every `RandomState` is seeded with fresh random keys, which is
+    // plenty of randomness for spreading retries over time.
+    let random = RandomState::new().build_hasher().finish();
+
+    Duration::from_nanos(random % max_nanos)
+}
+
 pub fn clamp(
     strategy: impl Iterator<Item = Duration>,
     max_delay: Duration,
@@ -138,6 +219,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exponential_backoff_is_capped() {
+        let strategy =
+            ExponentialBackoff::new(Duration::from_millis(100), Duration::from_millis(1000))
+                .without_jitter();
+
+        let delays = strategy.take(7).collect::<Vec<_>>();
+
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+                Duration::from_millis(800),
+                Duration::from_millis(1000),
+                Duration::from_millis(1000),
+                Duration::from_millis(1000)
+            ]
+        );
+    }
+
+    #[test]
+    fn exponential_backoff_jitter_is_bounded() {
+        const BASE: Duration = Duration::from_millis(100);
+        const MAX_DELAY: Duration = Duration::from_secs(10);
+
+        let strategy = ExponentialBackoff::new(BASE, MAX_DELAY);
+
+        for (attempt, delay) in strategy.take(10).enumerate() {
+            let min = (BASE * 2u32.pow(attempt as u32)).min(MAX_DELAY);
+            assert!(delay >= min && delay <= (min + BASE).min(MAX_DELAY));
+        }
+    }
+
+    #[test]
+    fn exponential_backoff_honors_factor() {
+        let strategy =
+            ExponentialBackoff::new(Duration::from_millis(100), Duration::from_millis(1000))
+                .with_factor(1.5)
+                .without_jitter();
+
+        let delays = strategy.take(6).collect::<Vec<_>>();
+
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(150),
+                Duration::from_millis(225),
+                Duration::from_micros(337_500),
+                Duration::from_micros(506_250),
+                Duration::from_micros(759_375),
+            ]
+        );
+    }
+
     #[test]
     fn clamped_total_const_growth_max_retries() {
         const MAX_DELAY: Duration = Duration::from_millis(500);
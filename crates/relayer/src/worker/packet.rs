@@ -219,7 +219,7 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
 use crate::link::RelaySummary;
 
 #[cfg(feature = "telemetry")]
-fn packet_metrics(path: &Packet, summary: &RelaySummary) {
+pub(crate) fn packet_metrics(path: &Packet, summary: &RelaySummary) {
     receive_packet_metrics(path, summary);
     acknowledgment_metrics(path, summary);
     timeout_metrics(path, summary);
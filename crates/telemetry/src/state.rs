@@ -91,6 +91,9 @@ pub struct TelemetryState {
     /// Number of misbehaviours detected and submitted per client
     client_misbehaviours_submitted: Counter<u64>,
 
+    /// Time left before a client expires, per client. Seconds.
+    client_expiry: ObservableGauge<u64>,
+
     /// Number of confirmed receive packets per channel
     receive_packets_confirmed: Counter<u64>,
 
@@ -100,6 +103,22 @@ pub struct TelemetryState {
     /// Number of confirmed timeout packets per channel
     timeout_packets_confirmed: Counter<u64>,
 
+    /// Number of channel opening handshakes, per outcome: started, opened or failed
+    channel_handshakes: Counter<u64>,
+
+    /// Number of attempts at the channel handshake steps, per step and status
+    handshake_steps: Counter<u64>,
+
+    /// Number of times a channel handshake step was retried, per step
+    handshake_retries: Counter<u64>,
+
+    /// How long the submission of a channel handshake step took, per step and status.
+    /// Milliseconds.
+    handshake_step_duration: ObservableGauge<u64>,
+
+    /// Number of failed attempts at the channel handshake steps, per step and error code
+    channel_handshake_errors: Counter<u64>,
+
     /// Number of queries submitted by Hermes, per chain and query type
     queries: Counter<u64>,
 
@@ -312,6 +331,25 @@ impl TelemetryState {
         self.client_updates_submitted.add(&cx, count, labels);
     }
 
+    /// Time left before a client expires, in seconds, per client
+    pub fn client_expiry(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        client: &ClientId,
+        seconds: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("client", client.to_string()),
+        ];
+
+        self.client_expiry.observe(&cx, seconds, labels);
+    }
+
     /// Number of client misbehaviours per client
     pub fn client_misbehaviours_submitted(
         &self,
@@ -395,6 +433,113 @@ impl TelemetryState {
         }
     }
 
+    /// Number of channel opening handshakes between chains a and b, per port on chain a
+    /// and outcome: started, opened or failed
+    pub fn channel_handshake(
+        &self,
+        a_chain: &ChainId,
+        b_chain: &ChainId,
+        port: &PortId,
+        outcome: &'static str,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("a_chain", a_chain.to_string()),
+            KeyValue::new("b_chain", b_chain.to_string()),
+            KeyValue::new("port", port.to_string()),
+            KeyValue::new("outcome", outcome),
+        ];
+
+        self.channel_handshakes.add(&cx, 1, labels);
+    }
+
+    /// Number of times a step of a channel opening handshake between chains a and b
+    /// was retried, per port on chain a and step
+    pub fn handshake_retries(
+        &self,
+        a_chain: &ChainId,
+        b_chain: &ChainId,
+        port: &PortId,
+        step: &str,
+        count: u64,
+    ) {
+        let cx = Context::current();
+
+        if count > 0 {
+            let labels = &[
+                KeyValue::new("a_chain", a_chain.to_string()),
+                KeyValue::new("b_chain", b_chain.to_string()),
+                KeyValue::new("port", port.to_string()),
+                KeyValue::new("step", step.to_string()),
+            ];
+
+            self.handshake_retries.add(&cx, count, labels);
+        }
+    }
+
+    /// Records an attempt at a channel handshake step submitted to the destination chain,
+    /// along with the time it took in milliseconds. The channel is the one of the
+    /// destination chain, if known.
+    #[allow(clippy::too_many_arguments)]
+    pub fn handshake_step(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        port: &PortId,
+        channel: Option<&ChannelId>,
+        step: &str,
+        status: &'static str,
+        duration_ms: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("port", port.to_string()),
+            KeyValue::new(
+                "channel",
+                channel.map(ToString::to_string).unwrap_or_default(),
+            ),
+            KeyValue::new("step", step.to_string()),
+            KeyValue::new("status", status),
+        ];
+
+        self.handshake_steps.add(&cx, 1, labels);
+        self.handshake_step_duration
+            .observe(&cx, duration_ms, labels);
+    }
+
+    /// Records a failed attempt at a channel handshake step submitted to the destination
+    /// chain, with the stable code of the error. The channel is the one of the
+    /// destination chain, if known.
+    pub fn channel_handshake_error(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        port: &PortId,
+        channel: Option<&ChannelId>,
+        step: &str,
+        code: &'static str,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("port", port.to_string()),
+            KeyValue::new(
+                "channel",
+                channel.map(ToString::to_string).unwrap_or_default(),
+            ),
+            KeyValue::new("step", step.to_string()),
+            KeyValue::new("code", code),
+        ];
+
+        self.channel_handshake_errors.add(&cx, 1, labels);
+    }
+
     /// Number of queries emitted by the relayer, per chain and query type
     pub fn query(&self, chain_id: &ChainId, query_type: &'static str) {
         let cx = Context::current();
@@ -780,6 +925,7 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "backlog_oldest_sequence" => Some(Arc::new(last_value())),
             "backlog_oldest_timestamp" => Some(Arc::new(last_value())),
             "backlog_size" => Some(Arc::new(last_value())),
+            "client_expiry" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.11.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
@@ -789,6 +935,9 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "tx_latency_confirmed" => Some(Arc::new(histogram(&[
                 1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0,
             ]))),
+            "handshake_step_duration" => Some(Arc::new(histogram(&[
+                1000.0, 5000.0, 10000.0, 20000.0, 30000.0, 60000.0,
+            ]))),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -830,6 +979,12 @@ impl Default for TelemetryState {
                 .with_description("Number of misbehaviours detected and submitted")
                 .init(),
 
+            client_expiry: meter
+                .u64_observable_gauge("client_expiry")
+                .with_unit(Unit::new("seconds"))
+                .with_description("Time left before a client expires, per client. Seconds.")
+                .init(),
+
             receive_packets_confirmed: meter
                 .u64_counter("receive_packets_confirmed")
                 .with_description("Number of confirmed receive packets. Available if relayer runs with Tx confirmation enabled")
@@ -845,6 +1000,32 @@ impl Default for TelemetryState {
                 .with_description("Number of confirmed timeout packets. Available if relayer runs with Tx confirmation enabled")
                 .init(),
 
+            channel_handshakes: meter
+                .u64_counter("channel_handshakes")
+                .with_description("Number of channel opening handshakes, per outcome: started, opened or failed")
+                .init(),
+
+            handshake_retries: meter
+                .u64_counter("handshake_retries")
+                .with_description("Number of times a channel handshake step was retried, per step")
+                .init(),
+
+            handshake_steps: meter
+                .u64_counter("handshake_steps")
+                .with_description("Number of attempts at the channel handshake steps, per step and status")
+                .init(),
+
+            handshake_step_duration: meter
+                .u64_observable_gauge("handshake_step_duration")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("How long the submission of a channel handshake step took, per step and status. Milliseconds.")
+                .init(),
+
+            channel_handshake_errors: meter
+                .u64_counter("channel_handshake_errors")
+                .with_description("Number of failed attempts at the channel handshake steps, per step and error code")
+                .init(),
+
             queries: meter
                 .u64_counter("queries")
                 .with_description(
@@ -189,6 +189,7 @@ pub fn pad_channel_id<ChainA: ChainHandle, ChainB: ChainHandle>(
                 None,
             ),
             connection_delay: connection.connection.delay_period,
+            overall_deadline: None,
         };
 
         channel.build_chan_open_init_and_send()?;
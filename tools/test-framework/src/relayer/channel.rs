@@ -2,7 +2,7 @@ use core::time::Duration;
 use eyre::eyre;
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::requests::{IncludeProof, QueryChannelRequest, QueryHeight};
-use ibc_relayer::channel::{extract_channel_id, Channel, ChannelSide};
+use ibc_relayer::channel::{Channel, ChannelSide};
 use ibc_relayer_types::core::ics04_channel::channel::State as ChannelState;
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, Order};
 
@@ -42,8 +42,9 @@ pub fn init_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
     src_port_id: &TaggedPortIdRef<ChainA, ChainB>,
     dst_port_id: &TaggedPortIdRef<ChainB, ChainA>,
 ) -> Result<(TaggedChannelId<ChainB, ChainA>, Channel<ChainB, ChainA>), Error> {
-    let channel = Channel {
+    let mut channel = Channel {
         connection_delay: Default::default(),
+        overall_deadline: None,
         ordering: Order::Unordered,
         a_side: ChannelSide::new(
             handle_a.clone(),
@@ -63,9 +64,9 @@ pub fn init_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
         ),
     };
 
-    let event = channel.build_chan_open_init_and_send()?;
-    let channel_id = extract_channel_id(&event)?.clone();
-    let channel2 = Channel::restore_from_event(handle_b.clone(), handle_a.clone(), event)?;
+    let outcome = channel.send_chan_open_init()?;
+    let channel_id = outcome.channel_id;
+    let channel2 = Channel::restore_from_event(handle_b.clone(), handle_a.clone(), outcome.event)?;
 
     Ok((DualTagged::new(channel_id), channel2))
 }
@@ -75,9 +76,10 @@ pub fn try_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
     handle_b: &ChainB,
     channel: &Channel<ChainB, ChainA>,
 ) -> Result<(TaggedChannelId<ChainA, ChainB>, Channel<ChainA, ChainB>), Error> {
-    let event = channel.build_chan_open_try_and_send()?;
-    let channel_id = extract_channel_id(&event)?.clone();
-    let channel2 = Channel::restore_from_event(handle_a.clone(), handle_b.clone(), event)?;
+    let mut channel = channel.clone();
+    let outcome = channel.send_chan_open_try()?;
+    let channel_id = outcome.channel_id;
+    let channel2 = Channel::restore_from_event(handle_a.clone(), handle_b.clone(), outcome.event)?;
 
     Ok((DualTagged::new(channel_id), channel2))
 }
@@ -87,9 +89,10 @@ pub fn ack_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
     handle_b: &ChainB,
     channel: &Channel<ChainB, ChainA>,
 ) -> Result<(TaggedChannelId<ChainA, ChainB>, Channel<ChainA, ChainB>), Error> {
-    let event = channel.build_chan_open_ack_and_send()?;
-    let channel_id = extract_channel_id(&event)?.clone();
-    let channel2 = Channel::restore_from_event(handle_a.clone(), handle_b.clone(), event)?;
+    let mut channel = channel.clone();
+    let outcome = channel.send_chan_open_ack()?;
+    let channel_id = outcome.channel_id;
+    let channel2 = Channel::restore_from_event(handle_a.clone(), handle_b.clone(), outcome.event)?;
 
     Ok((DualTagged::new(channel_id), channel2))
 }
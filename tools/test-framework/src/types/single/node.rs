@@ -157,6 +157,11 @@ impl FullNode {
             proof_specs: Default::default(),
             extension_options: Default::default(),
             sequential_batch_tx: false,
+            read_only: false,
+            handshake_retry: Default::default(),
+            handshake_timeout: config::default::handshake_timeout(),
+            client_refresh_threshold: config::default::client_refresh_threshold(),
+            handshake_proofs_at_trusted_height: false,
         })
     }
 